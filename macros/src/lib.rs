@@ -0,0 +1,98 @@
+//! Procedural macros for the [`sliceslice`
+//! crate](https://github.com/cloudflare/sliceslice-rs). They are re-exported by
+//! `sliceslice` when its `macros` feature is enabled and should be used from
+//! there.
+
+#![warn(missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, LitByteStr};
+
+/// Maximum number of bytes compared by an unrolled verification. Longer needles
+/// use the default comparison to avoid generating excessive amounts of code.
+const MAX_UNROLLED_SIZE: usize = 64;
+
+/// Creates a `sliceslice::Finder` fully specialized for a literal needle.
+///
+/// The `position` of the second byte used by the SIMD hash is selected at
+/// compile time as the least common byte of the needle, according to a table of
+/// byte frequencies. Candidate verifications compare the needle in a handful of
+/// unaligned word-sized loads against constants instead of calling a generic
+/// comparison, for needles of up to 65 bytes.
+///
+/// # Example
+///
+/// ```
+/// let finder = sliceslice::searcher!(b"Content-Length:");
+///
+/// assert!(finder.search_in(b"Host: example.com\r\nContent-Length: 42\r\n"));
+/// ```
+#[proc_macro]
+pub fn searcher(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitByteStr);
+    let needle = literal.value();
+
+    let eq_tail = match needle.get(1..) {
+        Some(tail) if tail.len() <= MAX_UNROLLED_SIZE => {
+            let eq = unrolled_eq(tail);
+            quote!(::core::option::Option::Some(#eq))
+        }
+        _ => quote!(::core::option::Option::None),
+    };
+
+    let expanded = quote! {{
+        #[derive(Clone, Copy, Debug)]
+        struct LiteralNeedle;
+
+        unsafe impl ::sliceslice::__private::LiteralNeedle for LiteralNeedle {
+            const BYTES: &'static [u8] = #literal;
+
+            #[inline(always)]
+            unsafe fn eq_tail(chunk: *const u8) -> ::core::option::Option<bool> {
+                unsafe { #eq_tail }
+            }
+        }
+
+        const POSITION: usize = ::sliceslice::__private::rarest_position(#literal);
+
+        ::sliceslice::Finder::__from_parts(
+            ::sliceslice::__private::Literal(LiteralNeedle),
+            POSITION,
+        )
+    }};
+
+    expanded.into()
+}
+
+/// Generates an expression comparing `tail` with the bytes at `chunk`, using
+/// the widest loads that fit. When `tail` is not a multiple of the load width,
+/// the last load overlaps with the previous one rather than using narrower
+/// loads.
+fn unrolled_eq(tail: &[u8]) -> TokenStream2 {
+    let (width, ty) = match tail.len() {
+        0 => return quote!(true),
+        1 => (1, quote!(u8)),
+        2..=3 => (2, quote!(u16)),
+        4..=7 => (4, quote!(u32)),
+        _ => (8, quote!(u64)),
+    };
+
+    let mut offsets = (0..tail.len() / width)
+        .map(|i| i * width)
+        .collect::<Vec<_>>();
+    if !tail.len().is_multiple_of(width) {
+        offsets.push(tail.len() - width);
+    }
+
+    let comparisons = offsets.into_iter().map(|offset| {
+        let bytes = &tail[offset..offset + width];
+        quote! {
+            ::core::ptr::read_unaligned(chunk.add(#offset) as *const #ty)
+                == #ty::from_ne_bytes([#(#bytes),*])
+        }
+    });
+
+    quote!(#(#comparisons)&&*)
+}
@@ -0,0 +1,109 @@
+/// Set of byte values, stored as a 256-bit bitmap.
+///
+/// It is used by whole-word searches to tell which bytes are part of words.
+///
+/// # Example
+///
+/// ```
+/// use sliceslice::ByteClass;
+///
+/// let class = ByteClass::word();
+/// assert!(class.contains(b'_'));
+/// assert!(!class.contains(b'-'));
+///
+/// let class = ByteClass::from_bytes(b"-.");
+/// assert!(class.contains(b'-'));
+/// assert!(!class.contains(b'_'));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ByteClass([u64; 4]);
+
+impl ByteClass {
+    /// Creates an empty class.
+    pub const fn new() -> Self {
+        Self([0; 4])
+    }
+
+    /// Creates the class of bytes found in identifiers, which are ASCII
+    /// letters, digits and the underscore.
+    pub const fn word() -> Self {
+        let mut class = Self::new();
+
+        let mut byte = 0u8;
+        while byte < 128 {
+            if byte.is_ascii_alphanumeric() || byte == b'_' {
+                class = class.with(byte);
+            }
+            byte += 1;
+        }
+
+        class
+    }
+
+    /// Creates the class of the given `bytes`.
+    pub const fn from_bytes(bytes: &[u8]) -> Self {
+        let mut class = Self::new();
+
+        let mut i = 0;
+        while i < bytes.len() {
+            class = class.with(bytes[i]);
+            i += 1;
+        }
+
+        class
+    }
+
+    const fn with(mut self, byte: u8) -> Self {
+        self.0[byte as usize / 64] |= 1 << (byte % 64);
+        self
+    }
+
+    /// Adds `byte` to the class.
+    #[inline]
+    pub fn insert(&mut self, byte: u8) {
+        *self = self.with(byte);
+    }
+
+    /// Returns `true` if `byte` is in the class.
+    #[inline]
+    pub const fn contains(&self, byte: u8) -> bool {
+        self.0[byte as usize / 64] >> (byte % 64) & 1 == 1
+    }
+
+    /// Returns the class of the bytes which are not in this one.
+    pub const fn complement(self) -> Self {
+        let [a, b, c, d] = self.0;
+        Self([!a, !b, !c, !d])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteClass;
+
+    #[test]
+    fn byte_class() {
+        let bytes = [0, 63, 64, b'a', 200, 255];
+
+        let mut class = ByteClass::new();
+        for byte in bytes {
+            class.insert(byte);
+        }
+        assert_eq!(class, ByteClass::from_bytes(&bytes));
+
+        for byte in 0..=255 {
+            assert_eq!(class.contains(byte), bytes.contains(&byte));
+            assert_eq!(class.complement().contains(byte), !bytes.contains(&byte));
+        }
+    }
+
+    #[test]
+    fn byte_class_word() {
+        let class = ByteClass::word();
+
+        for byte in 0..=255u8 {
+            let expected = byte.is_ascii_alphanumeric() || byte == b'_';
+            assert_eq!(class.contains(byte), expected);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{sealed::Sealed, Finder, Needle, NeedleWithSize};
+
+/// Offset into the values buffer of a column of strings.
+///
+/// This trait is sealed, and implemented for `u32`, `u64` and `usize` only.
+pub trait Offset: Copy + Sealed {
+    /// Converts the offset to an index into the values buffer.
+    fn to_usize(self) -> usize;
+}
+
+impl Sealed for u32 {}
+impl Sealed for u64 {}
+impl Sealed for usize {}
+
+impl Offset for u32 {
+    #[inline]
+    fn to_usize(self) -> usize {
+        self as usize
+    }
+}
+
+impl Offset for u64 {
+    #[inline]
+    fn to_usize(self) -> usize {
+        self as usize
+    }
+}
+
+impl Offset for usize {
+    #[inline]
+    fn to_usize(self) -> usize {
+        self
+    }
+}
+
+impl<N: Needle> Finder<N> {
+    /// Performs a substring search for the `needle` within each row of a
+    /// column of strings, stored as a single `values` buffer with row `i`
+    /// spanning `values[offsets[i]..offsets[i + 1]]`, like in Apache Arrow.
+    ///
+    /// The values buffer is scanned once rather than row by row, and each
+    /// occurrence is mapped back to the row in which it starts. Occurrences
+    /// straddling the boundary between two rows are ignored. Bit `i % 64` of
+    /// `out[i / 64]` is set if and only if row `i` contains the `needle`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out.len()` is not the number of rows divided by 64, rounded
+    /// up, or if `offsets` are out of bounds of `values`. The results are
+    /// unspecified if `offsets` are not increasing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sliceslice::Finder;
+    ///
+    /// let values = b"foobarbazqux";
+    /// let offsets: [u32; 5] = [0, 3, 6, 9, 12];
+    ///
+    /// let mut out = [0];
+    /// Finder::new(&b"ba"[..]).search_in_column(values, &offsets, &mut out);
+    ///
+    /// assert_eq!(out, [0b0110]);
+    /// ```
+    pub fn search_in_column<O: Offset>(&self, values: &[u8], offsets: &[O], out: &mut [u64]) {
+        let rows = offsets.len().saturating_sub(1);
+        assert_eq!(
+            rows.div_ceil(u64::BITS as usize),
+            out.len(),
+            "output length mismatch"
+        );
+
+        out.fill(0);
+        let mut set = |row: usize| out[row / 64] |= 1 << (row % 64);
+
+        let size = self.needle().size();
+        if size == 0 {
+            (0..rows).for_each(set);
+            return;
+        }
+
+        let (Some(first), Some(last)) = (offsets.first(), offsets.last()) else {
+            return;
+        };
+        let values = &values[..last.to_usize()];
+
+        let mut row = 0;
+        let mut position = first.to_usize();
+        while let Some(found) = self.find_in(&values[position..]) {
+            let start = position + found;
+
+            // Find the row in which the occurrence starts, among the rows
+            // following the current one.
+            row += offsets[row + 1..].partition_point(|offset| offset.to_usize() <= start);
+            let end = offsets[row + 1].to_usize();
+
+            // An occurrence straddling the end of the row cannot be followed by
+            // another one within the same row, so the search always resumes at
+            // the next row.
+            if start + size <= end {
+                set(row);
+            }
+
+            position = end;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Offset;
+    use crate::Finder;
+
+    fn search<O: Offset>(values: &[u8], offsets: &[O], needle: &[u8]) {
+        let rows = offsets.len().saturating_sub(1);
+
+        let mut out = vec![u64::MAX; rows.div_ceil(64)];
+        Finder::new(needle).search_in_column(values, offsets, &mut out);
+
+        for row in 0..rows {
+            let value = &values[offsets[row].to_usize()..offsets[row + 1].to_usize()];
+            let expected = needle.is_empty() || value.windows(needle.len()).any(|w| w == needle);
+            assert_eq!(out[row / 64] >> (row % 64) & 1 == 1, expected, "row {row}");
+        }
+
+        if !rows.is_multiple_of(64) {
+            assert_eq!(out[rows / 64] >> (rows % 64), 0);
+        }
+    }
+
+    #[test]
+    fn column_search() {
+        let mut values = Vec::new();
+        let mut offsets = vec![0u32];
+        for i in 0..200 {
+            match i % 5 {
+                0 => values.extend_from_slice(b"ab"),
+                1 => values.extend_from_slice(b"cab"),
+                2 => {}
+                3 => values.extend_from_slice(b"abcabc"),
+                _ => values.extend_from_slice(b"xyzxyzxyzxyzxyzxyzxyzxyzxyzxyzxyzabcab"),
+            }
+            offsets.push(values.len() as u32);
+        }
+
+        let offsets_u64 = offsets.iter().map(|&o| o as u64).collect::<Vec<_>>();
+
+        for needle in [
+            &b""[..],
+            b"a",
+            b"ab",
+            b"abc",
+            b"bca",
+            b"ca",
+            b"bc",
+            b"zabcab",
+            b"q",
+        ] {
+            search(&values, &offsets, needle);
+            search(&values, &offsets_u64, needle);
+            search(&values, &offsets[7..150], needle);
+            search(&values, &offsets_u64[64..129], needle);
+        }
+    }
+
+    #[test]
+    fn column_search_straddling() {
+        let values = b"abcabc";
+        let offsets: [u32; 4] = [0, 2, 4, 6];
+
+        let mut out = [0];
+        Finder::new(&b"abc"[..]).search_in_column(values, &offsets, &mut out);
+        assert_eq!(out, [0]);
+
+        Finder::new(&b"bc"[..]).search_in_column(values, &offsets, &mut out);
+        assert_eq!(out, [0b100]);
+    }
+
+    #[test]
+    fn column_search_empty() {
+        let mut out = [];
+        Finder::new(&b"a"[..]).search_in_column::<u32>(b"", &[], &mut out);
+        Finder::new(&b"a"[..]).search_in_column::<u32>(b"", &[0], &mut out);
+    }
+
+    #[test]
+    #[should_panic]
+    fn column_search_invalid_offsets() {
+        let mut out = [0];
+        Finder::new(&b"a"[..]).search_in_column::<u32>(b"abc", &[0, 4], &mut out);
+    }
+}
@@ -0,0 +1,936 @@
+#[cfg(all(target_arch = "aarch64", feature = "aarch64", target_feature = "neon"))]
+use crate::aarch64::NeonSearcher;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+use crate::wasm32::Wasm32Searcher;
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(feature = "std", target_feature = "avx2")
+))]
+use crate::x86::Avx2Searcher;
+use crate::{
+    find_aligned_with, find_word_with, memcmp, search_in_batch_bits_with, search_in_batch_with,
+    BudgetExceeded, ByteClass, CompiledNeedle, Needle, NeedleWithSize,
+};
+use core::ops::Range;
+use memchr::{memchr, memmem, memrchr};
+
+/// Runs `$method` on the best searcher available for the current CPU, built on
+/// the fly from the needle and `position` of `$finder`, or evaluates
+/// `$fallback` if no SIMD implementation can be used. Without the `std`
+/// feature, only the features enabled at compile time are considered.
+macro_rules! with_best_searcher {
+    ($finder:expr, $method:ident($($arg:expr),*), $fallback:expr) => {{
+        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "std"))]
+        if is_x86_feature_detected!("avx2") {
+            return unsafe {
+                Avx2Searcher::with_position(&$finder.needle, $finder.position).$method($($arg),*)
+            };
+        }
+
+        #[cfg(all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            not(feature = "std"),
+            target_feature = "avx2"
+        ))]
+        return unsafe {
+            Avx2Searcher::with_position(&$finder.needle, $finder.position).$method($($arg),*)
+        };
+
+        #[cfg(all(target_arch = "aarch64", feature = "aarch64", target_feature = "neon"))]
+        return unsafe {
+            NeonSearcher::with_position(&$finder.needle, $finder.position).$method($($arg),*)
+        };
+
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        return unsafe {
+            Wasm32Searcher::with_position(&$finder.needle, $finder.position).$method($($arg),*)
+        };
+
+        // The fallback ignores `position` and the arguments, which would be
+        // reported as unused when no SIMD implementation is compiled in.
+        #[allow(unreachable_code)]
+        {
+            let _ = (&$finder.position, $(&$arg),*);
+            $fallback
+        }
+    }};
+}
+
+/// Single-substring searcher that picks the best available implementation at
+/// search time.
+///
+/// Unlike the architecture-specific searchers, a `Finder` stores only the
+/// needle and `position`, and splats them into SIMD registers whenever a search
+/// is performed. This costs a handful of instructions per search, but means
+/// that a `Finder` can be created in a `const` or `static` context with
+/// `new_const`, and that it can be used safely since the availability of CPU
+/// features is checked before each search.
+///
+/// Needles of length zero are found in all haystacks and needles of length
+/// one use `memchr`. When no SIMD implementation is available, searching falls
+/// back to `memchr::memmem`. Without the `std` feature, CPU features cannot be
+/// detected at runtime and only those enabled at compile time are used.
+///
+/// # Example
+///
+/// ```
+/// use sliceslice::Finder;
+///
+/// static FINDER: Finder<[u8; 5]> = Finder::new_const(b"ipsum");
+///
+/// assert!(FINDER.search_in(b"Lorem ipsum dolor sit amet, consectetur adipiscing elit"));
+///
+/// assert!(!FINDER.search_in(b"foo bar baz qux quux quuz corge grault garply waldo fred"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Finder<N: Needle> {
+    needle: N,
+    position: usize,
+}
+
+impl<const K: usize> Finder<[u8; K]> {
+    /// Creates a new finder for the literal `needle` in a `const` context. By
+    /// default, `position` is set to the last character in the needle.
+    pub const fn new_const(needle: &[u8; K]) -> Self {
+        // Wrapping prevents panicking on unsigned integer underflow when
+        // `needle` is empty.
+        Self::with_position_const(needle, K.wrapping_sub(1))
+    }
+
+    /// Same as `new_const` but allows additionally specifying the `position` to
+    /// use.
+    ///
+    /// # Panics
+    ///
+    /// When `needle` is not empty, panics if `position` is not a valid index
+    /// for `needle`. When evaluated in a `const` or `static` context, this is
+    /// reported as a compilation error instead.
+    pub const fn with_position_const(needle: &[u8; K], position: usize) -> Self {
+        assert!(K == 0 || position < K, "position is out of bounds");
+
+        Self {
+            needle: *needle,
+            position,
+        }
+    }
+}
+
+impl<N: Needle> Finder<N> {
+    /// Creates a new finder for `needle`. By default, `position` is set to the
+    /// last character in the needle.
+    pub fn new(needle: N) -> Self {
+        Self::from_compiled(CompiledNeedle::new(needle))
+    }
+
+    /// Same as `new` but allows additionally specifying the `position` to use.
+    ///
+    /// # Panics
+    ///
+    /// When `needle` is not empty, panics if `position` is not a valid index
+    /// for `needle`, or if the associated `SIZE` constant does not correspond to
+    /// the actual size of `needle`.
+    pub fn with_position(needle: N, position: usize) -> Self {
+        if let Some(size) = N::SIZE {
+            assert_eq!(size, needle.as_bytes().len());
+        }
+
+        Self::from_compiled(CompiledNeedle::with_position(needle, position))
+    }
+
+    /// Creates a new finder without checking `position`, for use by the
+    /// `searcher!` macro which selects it at compile time.
+    #[doc(hidden)]
+    pub const fn __from_parts(needle: N, position: usize) -> Self {
+        Self { needle, position }
+    }
+
+    /// Creates a new finder from a `CompiledNeedle`, using its needle and
+    /// `position`.
+    pub fn from_compiled(compiled: CompiledNeedle<N>) -> Self {
+        let (needle, position) = compiled.into_parts();
+        Self { needle, position }
+    }
+
+    /// Returns the needle searched for by this finder.
+    pub fn needle(&self) -> &N {
+        &self.needle
+    }
+
+    /// Performs a substring search for the `needle` within `haystack`.
+    pub fn search_in(&self, haystack: &[u8]) -> bool {
+        match self.needle.size() {
+            0 => true,
+            1 => memchr(self.needle.as_bytes()[0], haystack).is_some(),
+            _ => with_best_searcher!(
+                self,
+                search_in(haystack),
+                memmem::find(haystack, self.needle.as_bytes()).is_some()
+            ),
+        }
+    }
+
+    /// Same as `search_in` but with a worst-case running time linear in the
+    /// lengths of `haystack` and `needle`.
+    pub fn search_in_linear(&self, haystack: &[u8]) -> bool {
+        match self.needle.size() {
+            0 => true,
+            1 => memchr(self.needle.as_bytes()[0], haystack).is_some(),
+            _ => with_best_searcher!(
+                self,
+                search_in_linear(haystack),
+                memmem::find(haystack, self.needle.as_bytes()).is_some()
+            ),
+        }
+    }
+
+    /// Same as `search_in` but gives up after `max_verifications` candidate
+    /// positions have been compared against the needle.
+    pub fn search_in_with_budget(
+        &self,
+        haystack: &[u8],
+        max_verifications: usize,
+    ) -> Result<bool, BudgetExceeded> {
+        match self.needle.size() {
+            0 => Ok(true),
+            1 => Ok(memchr(self.needle.as_bytes()[0], haystack).is_some()),
+            _ => with_best_searcher!(
+                self,
+                search_in_with_budget(haystack, max_verifications),
+                Ok(memmem::find(haystack, self.needle.as_bytes()).is_some())
+            ),
+        }
+    }
+
+    /// Performs a substring search for the `needle` within each of `haystacks`,
+    /// writing the results to the corresponding elements of `out`. CPU
+    /// features are only detected once for the whole batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `haystacks` and `out` have different lengths.
+    pub fn search_in_batch(&self, haystacks: &[&[u8]], out: &mut [bool]) {
+        match self.needle.size() {
+            0 => search_in_batch_with(haystacks, out, |_| true),
+            1 => search_in_batch_with(haystacks, out, |haystack| {
+                memchr(self.needle.as_bytes()[0], haystack).is_some()
+            }),
+            _ => with_best_searcher!(
+                self,
+                search_in_batch(haystacks, out),
+                search_in_batch_with(haystacks, out, |haystack| {
+                    memmem::find(haystack, self.needle.as_bytes()).is_some()
+                })
+            ),
+        }
+    }
+
+    /// Same as `search_in_batch` but writes the results as a bitset, where bit
+    /// `i % 64` of `out[i / 64]` is set if and only if `haystacks[i]` contains
+    /// the `needle`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out.len()` is not `haystacks.len().div_ceil(64)`.
+    pub fn search_in_batch_bits(&self, haystacks: &[&[u8]], out: &mut [u64]) {
+        match self.needle.size() {
+            0 => search_in_batch_bits_with(haystacks, out, |_| true),
+            1 => search_in_batch_bits_with(haystacks, out, |haystack| {
+                memchr(self.needle.as_bytes()[0], haystack).is_some()
+            }),
+            _ => with_best_searcher!(
+                self,
+                search_in_batch_bits(haystacks, out),
+                search_in_batch_bits_with(haystacks, out, |haystack| {
+                    memmem::find(haystack, self.needle.as_bytes()).is_some()
+                })
+            ),
+        }
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack`, if any.
+    pub fn find_in(&self, haystack: &[u8]) -> Option<usize> {
+        match self.needle.size() {
+            0 => Some(0),
+            1 => memchr(self.needle.as_bytes()[0], haystack),
+            _ => with_best_searcher!(
+                self,
+                find_in(haystack),
+                memmem::find(haystack, self.needle.as_bytes())
+            ),
+        }
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` starting at or after `start`, if any. The offset is relative
+    /// to the start of `haystack`, which allows resuming a search after a
+    /// previous occurrence.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is greater than the length of `haystack`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sliceslice::Finder;
+    ///
+    /// let finder = Finder::new(&b"ab"[..]);
+    /// let haystack = b"abcab";
+    ///
+    /// assert_eq!(finder.find_at(haystack, 0), Some(0));
+    /// assert_eq!(finder.find_at(haystack, 1), Some(3));
+    /// assert_eq!(finder.find_at(haystack, 4), None);
+    /// ```
+    pub fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize> {
+        match self.needle.size() {
+            0 | 1 => Some(start + self.find_in(&haystack[start..])?),
+            _ => with_best_searcher!(
+                self,
+                find_at(haystack, start),
+                Some(start + memmem::find(&haystack[start..], self.needle.as_bytes())?)
+            ),
+        }
+    }
+
+    /// Performs a substring search for the `needle` within `haystack[range]`.
+    ///
+    /// Unlike searching the subslice, the bytes of `haystack` after the end of
+    /// `range` may be read, so that the SIMD scan keeps using full vectors
+    /// instead of falling back to narrower ones near the end of the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of `haystack`.
+    pub fn search_in_range(&self, haystack: &[u8], range: Range<usize>) -> bool {
+        match self.needle.size() {
+            0 | 1 => self.search_in(&haystack[range]),
+            _ => with_best_searcher!(
+                self,
+                search_in_range(haystack, range.clone()),
+                memmem::find(&haystack[range], self.needle.as_bytes()).is_some()
+            ),
+        }
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` which is a whole word, i.e. which is neither preceded nor
+    /// followed by a byte of `class`, if any. Occurrences which are not whole
+    /// words are skipped without interrupting the SIMD scan.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sliceslice::{ByteClass, Finder};
+    ///
+    /// let finder = Finder::new(&b"id"[..]);
+    /// let haystack = b"if valid(id_list) { return id; }";
+    ///
+    /// assert_eq!(finder.find_word_in(haystack, &ByteClass::word()), Some(27));
+    /// assert_eq!(finder.find_word_in(haystack, &ByteClass::from_bytes(b"_")), Some(6));
+    /// ```
+    pub fn find_word_in(&self, haystack: &[u8], class: &ByteClass) -> Option<usize> {
+        self.find_word_at(haystack, 0, class)
+    }
+
+    /// Same as `find_word_in` but only reports occurrences starting at or
+    /// after `start`, like `find_at`. The byte before `start` is still checked
+    /// for whole words, so that a search resumed past a previous occurrence
+    /// reports the same occurrences as a search of the whole `haystack`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is greater than the length of `haystack`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sliceslice::{ByteClass, Finder};
+    ///
+    /// let finder = Finder::new(&b"id"[..]);
+    /// let haystack = b"xid id";
+    ///
+    /// assert_eq!(finder.find_word_at(haystack, 1, &ByteClass::word()), Some(4));
+    /// assert_eq!(finder.find_word_in(&haystack[1..], &ByteClass::word()), Some(0));
+    /// ```
+    pub fn find_word_at(&self, haystack: &[u8], start: usize, class: &ByteClass) -> Option<usize> {
+        let size = self.needle.size();
+        match size {
+            0 => find_word_with(haystack, start, 0, class, |_| Some(0)),
+            1 => find_word_with(haystack, start, 1, class, |rest| {
+                memchr(self.needle.as_bytes()[0], rest)
+            }),
+            _ => with_best_searcher!(
+                self,
+                find_word_at(haystack, start, class),
+                find_word_with(haystack, start, size, class, |rest| {
+                    memmem::find(rest, self.needle.as_bytes())
+                })
+            ),
+        }
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` at a multiple of `stride`, if any, such as the offsets of
+    /// fields in a table of aligned records. Candidates at other offsets are
+    /// masked out before being verified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sliceslice::Finder;
+    ///
+    /// let finder = Finder::new(&[0xef, 0xbe][..]);
+    /// let table = [0x00, 0x00, 0xef, 0xbe, 0xad, 0xde, 0x00, 0x00, 0xef, 0xbe, 0xad, 0xde];
+    ///
+    /// assert_eq!(finder.find_in(&table), Some(2));
+    /// assert_eq!(finder.find_aligned_in(&table, 4), Some(8));
+    /// ```
+    pub fn find_aligned_in(&self, haystack: &[u8], stride: usize) -> Option<usize> {
+        match self.needle.size() {
+            0 => find_aligned_with(haystack, stride, |_| Some(0)),
+            1 => find_aligned_with(haystack, stride, |rest| {
+                memchr(self.needle.as_bytes()[0], rest)
+            }),
+            _ => with_best_searcher!(
+                self,
+                find_aligned_in(haystack, stride),
+                find_aligned_with(haystack, stride, |rest| {
+                    memmem::find(rest, self.needle.as_bytes())
+                })
+            ),
+        }
+    }
+
+    /// Returns the offset of the last occurrence of the `needle` within
+    /// `haystack`, if any.
+    pub fn rfind_in(&self, haystack: &[u8]) -> Option<usize> {
+        match self.needle.size() {
+            0 => Some(haystack.len()),
+            1 => memrchr(self.needle.as_bytes()[0], haystack),
+            _ => with_best_searcher!(
+                self,
+                rfind_in(haystack),
+                memmem::rfind(haystack, self.needle.as_bytes())
+            ),
+        }
+    }
+
+    /// Returns the length of the longest suffix of `haystack` which is a
+    /// prefix of the `needle`, up to the length of the whole needle.
+    ///
+    /// This is the number of bytes at the end of a chunk of data, such as a
+    /// network packet, from which an occurrence of the `needle` may continue
+    /// into the next chunk. Each position where such a suffix could start is
+    /// found with `memchr` and checked using comparisons specialized for
+    /// small lengths.
+    ///
+    /// Each check compares up to the length of the needle, so that a call
+    /// takes time quadratic in the length of the needle in the worst case,
+    /// when its first byte repeats like in `aaaab` after a run of `a`s. To
+    /// stitch the chunks of a stream in linear time overall, use a
+    /// `StreamingMatcher`, which precomputes the borders of the needle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sliceslice::Finder;
+    ///
+    /// let finder = Finder::new(&b"needle"[..]);
+    ///
+    /// assert_eq!(finder.longest_suffix_prefix(b"hay hay nee"), 3);
+    /// assert_eq!(finder.longest_suffix_prefix(b"hay needle"), 6);
+    /// assert_eq!(finder.longest_suffix_prefix(b"hay needles"), 0);
+    /// ```
+    pub fn longest_suffix_prefix(&self, haystack: &[u8]) -> usize {
+        let needle = self.needle.as_bytes();
+        let Some(&first) = needle.first() else {
+            return 0;
+        };
+
+        // Longer suffixes start further left, so the first one found wins.
+        let suffix = &haystack[haystack.len().saturating_sub(needle.len())..];
+        let mut offset = 0;
+        while let Some(found) = memchr(first, &suffix[offset..]) {
+            let candidate = &suffix[offset + found..];
+
+            // The first bytes are already known to be equal.
+            let size = candidate.len() - 1;
+            if unsafe { memcmp::equal(candidate.as_ptr().add(1), needle.as_ptr().add(1), size) } {
+                return candidate.len();
+            }
+
+            offset += found + 1;
+        }
+
+        0
+    }
+
+    /// Returns the offset of the first position in `haystack` where the
+    /// `needle` could start, without verifying the bytes of the needle other
+    /// than the two used by the SIMD hash.
+    pub(crate) fn find_candidate_in(&self, haystack: &[u8]) -> Option<usize> {
+        match self.needle.size() {
+            0 => Some(0),
+            1 => memchr(self.needle.as_bytes()[0], haystack),
+            _ => with_best_searcher!(
+                self,
+                find_candidate_in(haystack),
+                find_candidate_fallback(self.needle.as_bytes(), self.position, haystack)
+            ),
+        }
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within the
+    /// string `haystack`, if any. The offset always lies on a character
+    /// boundary, so needles which are not valid UTF-8 are never found.
+    pub fn find_str(&self, haystack: &str) -> Option<usize> {
+        StrMatches::new(self, haystack).next()
+    }
+
+    /// Returns an iterator over the pieces of the string `haystack` separated
+    /// by non-overlapping occurrences of the `needle`, like `str::split`.
+    pub fn split_str<'f, 'h>(&'f self, haystack: &'h str) -> SplitStr<'f, 'h, N> {
+        SplitStr {
+            matches: StrMatches::new(self, haystack),
+            start: 0,
+            finished: false,
+        }
+    }
+
+    /// Returns an iterator over the non-overlapping occurrences of the `needle`
+    /// within the string `haystack`, along with their offsets, like
+    /// `str::match_indices`.
+    pub fn match_indices<'f, 'h>(&'f self, haystack: &'h str) -> MatchIndices<'f, 'h, N> {
+        MatchIndices(StrMatches::new(self, haystack))
+    }
+}
+
+/// Scalar equivalent of the SIMD candidate filter, looking for the first byte
+/// of `needle` with `memchr` and checking the byte at `position`.
+fn find_candidate_fallback(needle: &[u8], position: usize, haystack: &[u8]) -> Option<usize> {
+    let end = (haystack.len() + 1).checked_sub(needle.len())?;
+
+    let mut offset = 0;
+    while let Some(found) = memchr(needle[0], &haystack[offset..end]) {
+        let candidate = offset + found;
+        if haystack[candidate + position] == needle[position] {
+            return Some(candidate);
+        }
+
+        offset = candidate + 1;
+    }
+
+    None
+}
+
+/// Finds the offsets of the non-overlapping occurrences of a needle within a
+/// string, from left to right.
+///
+/// Occurrences of a valid UTF-8 needle within a string always start and end on
+/// character boundaries, while no occurrence of an invalid one does. Checking
+/// the needle once is therefore enough to make sure that slicing the haystack
+/// at the offsets found never panics. The empty needle matches at every
+/// character boundary.
+#[derive(Clone, Debug)]
+struct StrMatches<'f, 'h, N: Needle> {
+    finder: &'f Finder<N>,
+    haystack: &'h str,
+    position: usize,
+}
+
+impl<'f, 'h, N: Needle> StrMatches<'f, 'h, N> {
+    fn new(finder: &'f Finder<N>, haystack: &'h str) -> Self {
+        let position = match core::str::from_utf8(finder.needle.as_bytes()) {
+            Ok(_) => 0,
+            // Needles which are not valid UTF-8 never match.
+            Err(_) => haystack.len() + 1,
+        };
+
+        Self {
+            finder,
+            haystack,
+            position,
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.finder.needle.size()
+    }
+}
+
+impl<N: Needle> Iterator for StrMatches<'_, '_, N> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let rest = self.haystack.get(self.position..)?;
+        let start = self.position;
+
+        match self.size() {
+            0 => self.position += rest.chars().next().map_or(1, char::len_utf8),
+            size => match self.finder.find_in(rest.as_bytes()) {
+                Some(offset) => {
+                    self.position += offset + size;
+                    return Some(start + offset);
+                }
+                None => {
+                    self.position = self.haystack.len() + 1;
+                    return None;
+                }
+            },
+        }
+
+        Some(start)
+    }
+}
+
+/// Iterator over the pieces of a string separated by a needle, created by
+/// `Finder::split_str`.
+#[derive(Clone, Debug)]
+pub struct SplitStr<'f, 'h, N: Needle> {
+    matches: StrMatches<'f, 'h, N>,
+    start: usize,
+    finished: bool,
+}
+
+impl<'h, N: Needle> Iterator for SplitStr<'_, 'h, N> {
+    type Item = &'h str;
+
+    fn next(&mut self) -> Option<&'h str> {
+        if self.finished {
+            return None;
+        }
+
+        let haystack = self.matches.haystack;
+        match self.matches.next() {
+            Some(end) => {
+                let piece = &haystack[self.start..end];
+                self.start = end + self.matches.size();
+                Some(piece)
+            }
+            None => {
+                self.finished = true;
+                Some(&haystack[self.start..])
+            }
+        }
+    }
+}
+
+/// Iterator over the occurrences of a needle within a string and their offsets,
+/// created by `Finder::match_indices`.
+#[derive(Clone, Debug)]
+pub struct MatchIndices<'f, 'h, N: Needle>(StrMatches<'f, 'h, N>);
+
+impl<'h, N: Needle> Iterator for MatchIndices<'_, 'h, N> {
+    type Item = (usize, &'h str);
+
+    fn next(&mut self) -> Option<(usize, &'h str)> {
+        let start = self.0.next()?;
+        let end = start + self.0.size();
+        Some((start, &self.0.haystack[start..end]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_candidate_fallback, Finder};
+    use crate::{BudgetExceeded, ByteClass};
+    #[cfg(feature = "alloc")]
+    use std::borrow::Cow;
+
+    static IPSUM: Finder<[u8; 5]> = Finder::new_const(b"ipsum");
+
+    const EMPTY: Finder<[u8; 0]> = Finder::new_const(b"");
+
+    #[test]
+    fn finder_static() {
+        assert!(IPSUM.search_in(b"Lorem ipsum dolor sit amet"));
+        assert!(!IPSUM.search_in(b"Lorem dolor sit amet"));
+    }
+
+    #[test]
+    fn finder_empty() {
+        assert!(EMPTY.search_in(b""));
+        assert!(EMPTY.search_in(b"foo"));
+    }
+
+    #[test]
+    fn finder_str() {
+        let haystack = "foo, bär, baz, , qux";
+
+        for needle in ["", "a", ", ", "bär", "ä", "quux"] {
+            let finder = Finder::new(needle);
+
+            assert_eq!(finder.find_str(haystack), haystack.find(needle));
+            assert_eq!(
+                finder.split_str(haystack).collect::<Vec<_>>(),
+                haystack.split(needle).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                finder.match_indices(haystack).collect::<Vec<_>>(),
+                haystack.match_indices(needle).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn finder_string() {
+        let finder = Finder::new(String::from("aa"));
+        assert_eq!(
+            finder.match_indices("aaaaa").collect::<Vec<_>>(),
+            [(0, "aa"), (2, "aa")]
+        );
+
+        let finder = Finder::new(Cow::Borrowed("baz"));
+        assert_eq!(finder.find_str("foo, bär, baz, , qux"), Some(11));
+    }
+
+    #[test]
+    fn finder_str_invalid_utf8() {
+        let finder = Finder::new(&b"\xc3"[..]);
+
+        assert!(finder.search_in("bär".as_bytes()));
+        assert_eq!(finder.find_str("bär"), None);
+        assert_eq!(finder.split_str("bär").collect::<Vec<_>>(), ["bär"]);
+        assert_eq!(finder.match_indices("bär").next(), None);
+    }
+
+    #[test]
+    fn finder_candidate_fallback() {
+        let haystack = b"Lorem ipsum dolor sit amet";
+
+        assert_eq!(find_candidate_fallback(b"ipsum", 4, haystack), Some(6));
+        assert_eq!(find_candidate_fallback(b"sXXXa", 4, haystack), Some(18));
+        assert_eq!(find_candidate_fallback(b"amet", 3, haystack), Some(22));
+        assert_eq!(find_candidate_fallback(b"amets", 1, haystack), None);
+    }
+
+    #[test]
+    fn finder_word() {
+        fn naive(haystack: &[u8], start: usize, needle: &[u8], class: &ByteClass) -> Option<usize> {
+            (start..(haystack.len() + 1).saturating_sub(needle.len())).find(|&i| {
+                haystack[i..].starts_with(needle)
+                    && (i == 0 || !class.contains(haystack[i - 1]))
+                    && haystack
+                        .get(i + needle.len())
+                        .is_none_or(|&byte| !class.contains(byte))
+            })
+        }
+
+        let haystack = b"id valid idid id_x (id) _id identifier id; xid id9 id\nid-id solid id";
+
+        for class in [
+            ByteClass::word(),
+            ByteClass::from_bytes(b" "),
+            ByteClass::new(),
+            ByteClass::new().complement(),
+        ] {
+            for needle in [
+                &b""[..],
+                b"i",
+                b"d",
+                b"id",
+                b"lid",
+                b"d i",
+                b"id9 id",
+                b"solid id",
+            ] {
+                let finder = Finder::new(needle);
+
+                for end in 0..=haystack.len() {
+                    let haystack = &haystack[..end];
+                    assert_eq!(
+                        finder.find_word_in(haystack, &class),
+                        naive(haystack, 0, needle, &class)
+                    );
+                }
+
+                for start in 0..=haystack.len() {
+                    assert_eq!(
+                        finder.find_word_at(haystack, start, &class),
+                        naive(haystack, start, needle, &class)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn finder_aligned() {
+        let haystack = (0..200).map(|i| b"abcab"[i % 5]).collect::<Vec<_>>();
+
+        for needle in [
+            &b""[..],
+            b"a",
+            b"c",
+            b"ab",
+            b"ca",
+            b"bab",
+            b"abcababc",
+            b"x",
+        ] {
+            let finder = Finder::new(needle);
+
+            for stride in [1, 2, 3, 4, 7, 8, 16, 32, 33, 64, 200, usize::MAX] {
+                for end in 0..=haystack.len() {
+                    let haystack = &haystack[..end];
+                    let expected = (0..(haystack.len() + 1).saturating_sub(needle.len()))
+                        .step_by(stride)
+                        .find(|&i| haystack[i..].starts_with(needle));
+
+                    assert_eq!(
+                        finder.find_aligned_in(haystack, stride),
+                        expected,
+                        "{needle:?} {stride} {end}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "stride must not be zero")]
+    fn finder_aligned_zero() {
+        Finder::new(&b"ab"[..]).find_aligned_in(b"abab", 0);
+    }
+
+    #[test]
+    fn finder_longest_suffix_prefix() {
+        let haystack = b"abcabcabdabcabcabcabdabcabcabcabcabcabcabdabcabcabcabcabcabd";
+
+        for needle in [
+            &b""[..],
+            b"a",
+            b"ab",
+            b"abd",
+            b"abcabd",
+            b"abcabcabd",
+            b"abcabcabcabcabcabcabd",
+            b"abcabcabcabcabcabcabcabcabcabcabcabcabcabcabcabcabcabcabcabcabcabd",
+            b"x",
+        ] {
+            let finder = Finder::new(needle);
+
+            for end in 0..=haystack.len() {
+                let haystack = &haystack[..end];
+                let expected = (0..=needle.len().min(end))
+                    .rev()
+                    .find(|&size| haystack.ends_with(&needle[..size]))
+                    .unwrap();
+
+                assert_eq!(
+                    finder.longest_suffix_prefix(haystack),
+                    expected,
+                    "{needle:?} {end}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn finder_longest_suffix_prefix_periodic() {
+        // Every byte of the suffix is a candidate, each compared up to the
+        // length of the needle.
+        let mut needle = vec![b'a'; 255];
+        needle.push(b'b');
+        let finder = Finder::new(&needle[..]);
+
+        let mut haystack = vec![b'a'; 1024];
+        assert_eq!(finder.longest_suffix_prefix(&haystack[..100]), 100);
+        assert_eq!(finder.longest_suffix_prefix(&haystack), 255);
+
+        haystack.push(b'b');
+        assert_eq!(finder.longest_suffix_prefix(&haystack), 256);
+        assert_eq!(finder.longest_suffix_prefix(&haystack[1000..]), 0);
+    }
+
+    #[test]
+    fn finder_batch() {
+        let haystacks = (0..130)
+            .map(|i| match i % 3 {
+                0 => &b"Lorem ipsum dolor sit amet"[..],
+                _ => &b"Lorem dolor sit amet"[..],
+            })
+            .collect::<Vec<_>>();
+
+        let mut out = vec![false; haystacks.len()];
+        IPSUM.search_in_batch(&haystacks, &mut out);
+        assert!(out
+            .iter()
+            .enumerate()
+            .all(|(i, &found)| found == (i % 3 == 0)));
+
+        let mut bits = [0; 3];
+        IPSUM.search_in_batch_bits(&haystacks, &mut bits);
+        for (i, &found) in out.iter().enumerate() {
+            assert_eq!(bits[i / 64] >> (i % 64) & 1 == 1, found);
+        }
+        assert_eq!(bits[2] >> 2, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn finder_batch_invalid_output() {
+        IPSUM.search_in_batch_bits(&[&b"ipsum"[..]; 65], &mut [0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn finder_invalid_position() {
+        Finder::with_position_const(b"foo", 3);
+    }
+
+    impl crate::tests::TestSearcher for Finder<&[u8]> {
+        fn with_position(needle: &'static [u8], position: usize) -> Finder<&'static [u8]> {
+            Finder::with_position(needle, position)
+        }
+
+        fn search_in(&self, haystack: &[u8]) -> bool {
+            Finder::search_in(self, haystack)
+        }
+
+        fn search_in_linear(&self, haystack: &[u8]) -> bool {
+            Finder::search_in_linear(self, haystack)
+        }
+
+        fn search_in_with_budget(
+            &self,
+            haystack: &[u8],
+            max_verifications: usize,
+        ) -> Result<bool, BudgetExceeded> {
+            Finder::search_in_with_budget(self, haystack, max_verifications)
+        }
+
+        fn find_in(&self, haystack: &[u8]) -> Option<usize> {
+            Finder::find_in(self, haystack)
+        }
+
+        fn rfind_in(&self, haystack: &[u8]) -> Option<usize> {
+            Finder::rfind_in(self, haystack)
+        }
+
+        fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize> {
+            Finder::find_at(self, haystack, start)
+        }
+
+        fn search_in_range(&self, haystack: &[u8], range: core::ops::Range<usize>) -> bool {
+            Finder::search_in_range(self, haystack, range)
+        }
+
+        fn search_in_batch(&self, haystacks: &[&[u8]], out: &mut [bool]) {
+            Finder::search_in_batch(self, haystacks, out)
+        }
+
+        fn search_in_batch_bits(&self, haystacks: &[&[u8]], out: &mut [u64]) {
+            Finder::search_in_batch_bits(self, haystacks, out)
+        }
+    }
+
+    crate::generate_tests!(finder, Finder);
+}
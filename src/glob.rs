@@ -0,0 +1,318 @@
+use crate::{
+    wildcard::{Token, WildcardMatcher},
+    ByteClass,
+};
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// Error returned when compiling an invalid glob pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GlobError {
+    offset: usize,
+    kind: GlobErrorKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum GlobErrorKind {
+    TrailingEscape,
+    UnclosedClass,
+}
+
+impl GlobError {
+    /// Returns the offset in the pattern of the trailing escape character or
+    /// of the opening bracket of the unclosed class.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            GlobErrorKind::TrailingEscape => write!(
+                f,
+                "glob pattern ends with an escape character at offset {}",
+                self.offset
+            ),
+            GlobErrorKind::UnclosedClass => write!(
+                f,
+                "unclosed character class starting at offset {} of the glob pattern",
+                self.offset
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for GlobError {}
+
+/// Matcher for shell glob patterns over bytes.
+///
+/// In a pattern, `*` matches any sequence of bytes, including an empty one and
+/// including `/`, and `?` matches exactly one byte. A class such as `[a-z_]`
+/// matches one byte among those listed, where ranges are inclusive, and
+/// `[!...]` or `[^...]` matches one byte which is not listed. A `]` listed
+/// first or a `-` listed first or last is taken literally. A backslash makes
+/// the byte following it match literally, both inside and outside classes.
+///
+/// The literal segments between `*` are looked for using a `Finder`, falling
+/// back to checking each byte only for `?` and classes.
+///
+/// # Example
+///
+/// ```
+/// use sliceslice::GlobMatcher;
+///
+/// let matcher = GlobMatcher::new(b"logs/*/app-202[0-9]-??.log").unwrap();
+/// assert!(matcher.matches(b"logs/eu-west/app-2024-03.log"));
+/// assert!(!matcher.matches(b"logs/eu-west/app-2024-3.log"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct GlobMatcher {
+    matcher: WildcardMatcher,
+}
+
+impl GlobMatcher {
+    /// Compiles the glob `pattern`.
+    pub fn new(pattern: &[u8]) -> Result<Self, GlobError> {
+        let mut segments = Vec::new();
+        let mut segment = Vec::new();
+
+        let mut i = 0;
+        while let Some(&byte) = pattern.get(i) {
+            match byte {
+                b'*' => segments.push(core::mem::take(&mut segment)),
+                b'?' => segment.push(Token::Any),
+                b'[' => {
+                    let (set, end) = parse_class(pattern, i)?;
+                    segment.push(Token::Class(set));
+                    i = end;
+                    continue;
+                }
+                b'\\' => match pattern.get(i + 1) {
+                    Some(&byte) => {
+                        segment.push(Token::Byte(byte));
+                        i += 1;
+                    }
+                    None => {
+                        return Err(GlobError {
+                            offset: i,
+                            kind: GlobErrorKind::TrailingEscape,
+                        })
+                    }
+                },
+                _ => segment.push(Token::Byte(byte)),
+            }
+
+            i += 1;
+        }
+        segments.push(segment);
+
+        Ok(Self {
+            matcher: WildcardMatcher::new(segments, false),
+        })
+    }
+
+    /// Returns `true` if the whole `haystack` matches the pattern.
+    pub fn matches(&self, haystack: &[u8]) -> bool {
+        self.matcher.matches(haystack)
+    }
+}
+
+/// Parses the class whose opening bracket is at `start` in `pattern`. Returns
+/// the set of bytes matched by the class and the offset following its closing
+/// bracket.
+fn parse_class(pattern: &[u8], start: usize) -> Result<(ByteClass, usize), GlobError> {
+    let error = GlobError {
+        offset: start,
+        kind: GlobErrorKind::UnclosedClass,
+    };
+
+    // Returns the byte at `i`, taken literally if escaped, along with the
+    // offset following it.
+    let byte_at = |i: usize| match pattern.get(i) {
+        Some(b'\\') => pattern.get(i + 1).map(|&byte| (byte, i + 2)),
+        Some(&byte) => Some((byte, i + 1)),
+        None => None,
+    };
+
+    let mut i = start + 1;
+    let negated = matches!(pattern.get(i), Some(b'!' | b'^'));
+    if negated {
+        i += 1;
+    }
+
+    let mut set = ByteClass::new();
+    let mut is_first = true;
+    loop {
+        if pattern.get(i) == Some(&b']') && !is_first {
+            break;
+        }
+        is_first = false;
+
+        let (low, next) = byte_at(i).ok_or(error)?;
+        i = next;
+
+        let high = match (pattern.get(i), pattern.get(i + 1)) {
+            (Some(b'-'), Some(&byte)) if byte != b']' => {
+                let (high, next) = byte_at(i + 1).ok_or(error)?;
+                i = next;
+                high
+            }
+            _ => low,
+        };
+
+        for byte in low..=high {
+            set.insert(byte);
+        }
+    }
+
+    match negated {
+        true => Ok((set.complement(), i + 1)),
+        false => Ok((set, i + 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GlobError, GlobErrorKind, GlobMatcher};
+
+    /// Naive backtracking implementation of globs, without classes and
+    /// escaping.
+    fn naive(pattern: &[u8], haystack: &[u8]) -> bool {
+        match pattern.split_first() {
+            None => haystack.is_empty(),
+            Some((b'*', rest)) => (0..=haystack.len()).any(|i| naive(rest, &haystack[i..])),
+            Some((&expected, rest)) => match haystack.split_first() {
+                Some((&byte, haystack)) => {
+                    (expected == b'?' || expected == byte) && naive(rest, haystack)
+                }
+                None => false,
+            },
+        }
+    }
+
+    #[test]
+    fn glob_naive() {
+        let haystacks = [
+            &b""[..],
+            b"a",
+            b"ab",
+            b"abc",
+            b"abcabc",
+            b"xyzabcxyz",
+            b"a/b/c/d",
+            b"photos/2024/01/img_0001.jpg",
+            b"photos/2024/01/img_0001.jpg.bak",
+        ];
+        let patterns = [
+            &b""[..],
+            b"*",
+            b"**",
+            b"?",
+            b"??",
+            b"a",
+            b"a*",
+            b"*a",
+            b"*a*",
+            b"a*c",
+            b"a?c",
+            b"*b?*",
+            b"*abc*abc*",
+            b"*abc*abc",
+            b"abc*abc",
+            b"*c*b*",
+            b"*?/?*",
+            b"a*/*/*d",
+            b"photos/*/*.jpg",
+            b"photos/????/??/img_*",
+            b"*/01/*0001*",
+            b"*?*?*?*?*",
+            b"*x?z*",
+        ];
+
+        for pattern in patterns {
+            let matcher = GlobMatcher::new(pattern).unwrap();
+
+            for haystack in haystacks {
+                assert_eq!(
+                    matcher.matches(haystack),
+                    naive(pattern, haystack),
+                    "{:?}",
+                    (
+                        core::str::from_utf8(pattern).unwrap(),
+                        core::str::from_utf8(haystack).unwrap(),
+                    )
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn glob_classes() {
+        let matcher = GlobMatcher::new(b"*img_[0-9][0-9][0-9][0-9].[jp][pn]g").unwrap();
+        assert!(matcher.matches(b"photos/img_0001.jpg"));
+        assert!(matcher.matches(b"img_1234.png"));
+        assert!(!matcher.matches(b"img_12a4.png"));
+        assert!(!matcher.matches(b"img_1234.gif"));
+
+        let matcher = GlobMatcher::new(b"*[!a-z]").unwrap();
+        assert!(matcher.matches(b"abc1"));
+        assert!(!matcher.matches(b"abc"));
+        assert!(GlobMatcher::new(b"[^a-z]").unwrap().matches(b"A"));
+
+        let matcher = GlobMatcher::new(b"[]a-]").unwrap();
+        assert!(matcher.matches(b"]"));
+        assert!(matcher.matches(b"a"));
+        assert!(matcher.matches(b"-"));
+        assert!(!matcher.matches(b"b"));
+
+        let matcher = GlobMatcher::new(b"[!]]").unwrap();
+        assert!(!matcher.matches(b"]"));
+        assert!(matcher.matches(b"a"));
+
+        let matcher = GlobMatcher::new(b"[\\]\\-]").unwrap();
+        assert!(matcher.matches(b"]"));
+        assert!(matcher.matches(b"-"));
+        assert!(!matcher.matches(b"\\"));
+
+        // Classes may also be part of the segments searched for.
+        let matcher = GlobMatcher::new(b"*/[0-9][0-9]/*").unwrap();
+        assert!(matcher.matches(b"photos/2024/01/img.jpg"));
+        assert!(!matcher.matches(b"photos/2024/1/img.jpg"));
+    }
+
+    #[test]
+    fn glob_escape() {
+        let matcher = GlobMatcher::new(b"\\*\\?\\[*").unwrap();
+        assert!(matcher.matches(b"*?[foo"));
+        assert!(!matcher.matches(b"a?[foo"));
+        assert!(!matcher.matches(b"*a[foo"));
+    }
+
+    #[test]
+    fn glob_invalid() {
+        let error = GlobMatcher::new(b"foo\\").unwrap_err();
+        assert_eq!(error.kind, GlobErrorKind::TrailingEscape);
+        assert_eq!(error.offset(), 3);
+
+        for (pattern, offset) in [
+            (&b"foo["[..], 3),
+            (b"foo[a-", 3),
+            (b"[]", 0),
+            (b"*[!]", 1),
+            (b"[a\\", 0),
+            (b"[a-\\", 0),
+        ] {
+            assert_eq!(
+                GlobMatcher::new(pattern).unwrap_err(),
+                GlobError {
+                    offset,
+                    kind: GlobErrorKind::UnclosedClass
+                }
+            );
+        }
+    }
+}
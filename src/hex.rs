@@ -0,0 +1,507 @@
+use crate::{rank::rarest_position, wildcard::Token, ByteClass, Finder};
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// Maximum number of combinations of alternatives in a pattern.
+const MAX_COMBINATIONS: usize = 1 << 16;
+
+/// Error returned when compiling an invalid hex pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HexError {
+    column: usize,
+    kind: HexErrorKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HexErrorKind {
+    InvalidCharacter,
+    IncompleteByte,
+    UnclosedAlternative,
+    EmptyAlternative,
+    TooManyAlternatives,
+}
+
+impl HexError {
+    /// Returns the column in the pattern, starting from 1, of the invalid
+    /// character, of the first digit of the incomplete byte, of the opening
+    /// parenthesis of the unclosed alternative, of the delimiter following
+    /// the empty alternative, or of the opening parenthesis of the alternative
+    /// bringing the number of combinations of alternatives over the limit.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            HexErrorKind::InvalidCharacter => write!(
+                f,
+                "invalid character at column {} of the hex pattern",
+                self.column
+            ),
+            HexErrorKind::IncompleteByte => write!(
+                f,
+                "incomplete byte at column {} of the hex pattern",
+                self.column
+            ),
+            HexErrorKind::UnclosedAlternative => write!(
+                f,
+                "unclosed alternative starting at column {} of the hex pattern",
+                self.column
+            ),
+            HexErrorKind::EmptyAlternative => write!(
+                f,
+                "empty alternative at column {} of the hex pattern",
+                self.column
+            ),
+            HexErrorKind::TooManyAlternatives => write!(
+                f,
+                "too many combinations of alternatives at column {} of the hex pattern",
+                self.column
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for HexError {}
+
+/// Searcher for byte signatures written as hex strings, in the style of YARA
+/// and IDA.
+///
+/// A pattern is a sequence of bytes written as two hex digits, in either case,
+/// and optionally separated by whitespace. A `?` in place of a digit matches
+/// any value of that nibble, so that `??` matches any byte and `4?` matches
+/// any byte from `0x40` to `0x4F`. Alternatives such as `(AA | BB CC)` match
+/// any of the sequences between the `|` separators, which may have different
+/// lengths and may themselves contain alternatives. A pattern may have at most
+/// 65536 combinations of alternatives.
+///
+/// The longest run of fully specified bytes outside of any alternative is
+/// searched for using a `Finder`, and the whole pattern is verified around
+/// each of its occurrences, trying every alternative at once.
+///
+/// # Example
+///
+/// ```
+/// use sliceslice::HexSearcher;
+///
+/// let searcher = HexSearcher::new("DE AD ?? EF (01 | 02 03) 4?").unwrap();
+/// assert_eq!(searcher.find_in(b"\x00\xde\xad\xbe\xef\x02\x03\x41"), Some(1));
+/// assert_eq!(searcher.find_in(b"\x00\xde\xad\xbe\xef\x02\x41"), None);
+///
+/// let error = HexSearcher::new("DE AD B").unwrap_err();
+/// assert_eq!(error.column(), 7);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HexSearcher {
+    items: Box<[Item]>,
+    // `None` if the pattern has no fully specified byte outside of any
+    // alternative, in which case every position is a candidate.
+    anchor: Option<Finder<Box<[u8]>>>,
+    // Possible offsets of the anchor from the start of a match, in decreasing
+    // order.
+    offsets: Box<[usize]>,
+}
+
+/// Element of a hex pattern.
+#[derive(Clone, Debug)]
+enum Item {
+    /// Single byte, possibly with wildcard nibbles.
+    Token(Token),
+    /// Sequences of which any one must match.
+    Alternatives(Box<[Box<[Item]>]>),
+}
+
+impl HexSearcher {
+    /// Compiles the hex `pattern`.
+    pub fn new(pattern: &str) -> Result<Self, HexError> {
+        let mut parser = Parser {
+            pattern,
+            position: 0,
+        };
+
+        let (items, _) = parser.parse_sequence()?;
+        if parser.position < pattern.len() {
+            return Err(parser.error(parser.position, HexErrorKind::InvalidCharacter));
+        }
+
+        let mut run = 0..0;
+        let mut start = 0;
+        for (i, item) in items.iter().enumerate() {
+            if !matches!(item, Item::Token(Token::Byte(_))) {
+                start = i + 1;
+            } else if i + 1 - start > run.len() {
+                run = start..i + 1;
+            }
+        }
+
+        let needle = items[run.clone()]
+            .iter()
+            .filter_map(|item| match item {
+                Item::Token(Token::Byte(byte)) => Some(*byte),
+                _ => None,
+            })
+            .collect::<Box<[u8]>>();
+        let anchor = (!needle.is_empty()).then(|| {
+            let position = rarest_position(&needle);
+            Finder::with_position(needle, position)
+        });
+
+        let mut offsets = ends(&items[..run.start], None, vec![0]);
+        offsets.reverse();
+
+        Ok(Self {
+            items: items.into_boxed_slice(),
+            anchor,
+            offsets: offsets.into_boxed_slice(),
+        })
+    }
+
+    /// Returns the offset of the first match of the pattern within
+    /// `haystack`, if any.
+    pub fn find_in(&self, haystack: &[u8]) -> Option<usize> {
+        let Some(anchor) = &self.anchor else {
+            return (0..=haystack.len()).find(|&start| self.is_match_at(haystack, start));
+        };
+
+        // Occurrences of the anchor are found in order, but a later one may
+        // still belong to an earlier match when alternatives before it have
+        // different lengths.
+        let farthest = self.offsets.first().copied().unwrap_or_default();
+        let mut first: Option<usize> = None;
+        let mut position = 0;
+        while let Some(found) = anchor.find_at(haystack, position) {
+            if first.is_some_and(|first| found >= first + farthest) {
+                break;
+            }
+
+            let candidate = self
+                .offsets
+                .iter()
+                .filter_map(|&offset| found.checked_sub(offset))
+                .filter(|&start| first.is_none_or(|first| start < first))
+                .find(|&start| self.is_match_at(haystack, start));
+            if candidate.is_some() {
+                first = candidate;
+            }
+
+            position = found + 1;
+        }
+
+        first
+    }
+
+    /// Performs a search for the pattern within `haystack`, returning `true`
+    /// if it is found.
+    pub fn search_in(&self, haystack: &[u8]) -> bool {
+        self.find_in(haystack).is_some()
+    }
+
+    /// Returns `true` if a match of the pattern starts at offset `start` of
+    /// `haystack`.
+    fn is_match_at(&self, haystack: &[u8], start: usize) -> bool {
+        !ends(&self.items, Some(haystack), vec![start]).is_empty()
+    }
+}
+
+/// Returns the offsets, in increasing order and without duplicates, at which
+/// a match of `items` starting at any of the `starts` offsets can end. Matches
+/// are checked against `haystack` if given, and only their lengths are
+/// considered otherwise.
+///
+/// Keeping a set of offsets rather than trying each combination of
+/// alternatives in turn bounds the work by the length of the pattern times the
+/// number of offsets.
+fn ends(items: &[Item], haystack: Option<&[u8]>, mut starts: Vec<usize>) -> Vec<usize> {
+    for item in items {
+        starts = match item {
+            Item::Token(token) => starts
+                .into_iter()
+                .filter(|&start| {
+                    haystack.is_none_or(|haystack| {
+                        haystack
+                            .get(start)
+                            .is_some_and(|&byte| token.matches(byte, false))
+                    })
+                })
+                .map(|start| start + 1)
+                .collect(),
+            Item::Alternatives(alternatives) => {
+                let mut ends = alternatives
+                    .iter()
+                    .flat_map(|alternative| ends(alternative, haystack, starts.clone()))
+                    .collect::<Vec<_>>();
+                ends.sort_unstable();
+                ends.dedup();
+                ends
+            }
+        };
+
+        if starts.is_empty() {
+            break;
+        }
+    }
+
+    starts
+}
+
+struct Parser<'a> {
+    pattern: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, offset: usize, kind: HexErrorKind) -> HexError {
+        HexError {
+            column: self.pattern[..offset].chars().count() + 1,
+            kind,
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        let bytes = self.pattern.as_bytes();
+        while bytes
+            .get(self.position)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.position += 1;
+        }
+
+        bytes.get(self.position).copied()
+    }
+
+    /// Parses a sequence of bytes and alternatives up to a `|` or `)`, or to
+    /// the end of the pattern. Returns it along with its number of
+    /// combinations of alternatives.
+    fn parse_sequence(&mut self) -> Result<(Vec<Item>, usize), HexError> {
+        let mut items = Vec::new();
+        let mut combinations = 1usize;
+
+        loop {
+            match self.peek() {
+                None | Some(b'|' | b')') => return Ok((items, combinations)),
+                Some(b'(') => {
+                    let start = self.position;
+                    let (alternatives, count) = self.parse_alternatives()?;
+
+                    combinations = combinations.saturating_mul(count);
+                    if combinations > MAX_COMBINATIONS {
+                        return Err(self.error(start, HexErrorKind::TooManyAlternatives));
+                    }
+                    items.push(alternatives);
+                }
+                Some(_) => items.push(Item::Token(self.parse_byte()?)),
+            }
+        }
+    }
+
+    /// Parses alternatives, starting at their opening parenthesis. Returns
+    /// them along with their number of combinations.
+    fn parse_alternatives(&mut self) -> Result<(Item, usize), HexError> {
+        let start = self.position;
+        self.position += 1;
+
+        let mut alternatives = Vec::new();
+        let mut combinations = 0usize;
+        loop {
+            let before = self.position;
+            let (items, count) = self.parse_sequence()?;
+
+            // Only whitespace before the delimiter means that the alternative
+            // is empty.
+            let delimiter = self.position;
+            if self.pattern[before..delimiter].trim().is_empty() {
+                return Err(match self.pattern.as_bytes().get(delimiter) {
+                    Some(_) => self.error(delimiter, HexErrorKind::EmptyAlternative),
+                    None => self.error(start, HexErrorKind::UnclosedAlternative),
+                });
+            }
+            alternatives.push(items.into_boxed_slice());
+            combinations = combinations.saturating_add(count);
+
+            self.position += 1;
+            match self.pattern.as_bytes().get(delimiter) {
+                Some(b'|') => {}
+                Some(_) => {
+                    let alternatives = Item::Alternatives(alternatives.into_boxed_slice());
+                    return Ok((alternatives, combinations));
+                }
+                None => return Err(self.error(start, HexErrorKind::UnclosedAlternative)),
+            }
+        }
+    }
+
+    /// Parses a byte made of two hex digits or `?` wildcards.
+    fn parse_byte(&mut self) -> Result<Token, HexError> {
+        let bytes = self.pattern.as_bytes();
+        let start = self.position;
+
+        let nibble = |i: usize| match bytes.get(i) {
+            Some(b'?') => Ok(None),
+            Some(&byte) if byte.is_ascii_hexdigit() => {
+                Ok(Some((byte as char).to_digit(16).unwrap() as u8))
+            }
+            Some(byte) if !byte.is_ascii_whitespace() && !b"()|".contains(byte) => {
+                Err(self.error(i, HexErrorKind::InvalidCharacter))
+            }
+            _ => Err(self.error(start, HexErrorKind::IncompleteByte)),
+        };
+
+        let high = match bytes[start] {
+            b'?' => None,
+            byte if byte.is_ascii_hexdigit() => nibble(start)?,
+            _ => return Err(self.error(start, HexErrorKind::InvalidCharacter)),
+        };
+        let low = nibble(start + 1)?;
+        self.position += 2;
+
+        Ok(match (high, low) {
+            (Some(high), Some(low)) => Token::Byte(high << 4 | low),
+            (None, None) => Token::Any,
+            (high, low) => {
+                let mut class = ByteClass::new();
+                for byte in 0..=u8::MAX {
+                    if high.is_none_or(|high| byte >> 4 == high)
+                        && low.is_none_or(|low| byte & 0xf == low)
+                    {
+                        class.insert(byte);
+                    }
+                }
+                Token::Class(class)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HexError, HexErrorKind, HexSearcher};
+
+    #[test]
+    fn hex_bytes() {
+        let searcher = HexSearcher::new("de AD\tBeEf").unwrap();
+        assert_eq!(searcher.find_in(b"\x00\x00\xde\xad\xbe\xef"), Some(2));
+        assert_eq!(searcher.find_in(b"\x00\x00\xde\xad\xbe\xee"), None);
+
+        let searcher = HexSearcher::new("DEADBEEF").unwrap();
+        assert_eq!(searcher.find_in(b"\xde\xad\xbe\xef"), Some(0));
+
+        let searcher = HexSearcher::new("").unwrap();
+        assert_eq!(searcher.find_in(b""), Some(0));
+        assert_eq!(searcher.find_in(b"abc"), Some(0));
+    }
+
+    #[test]
+    fn hex_wildcards() {
+        let searcher = HexSearcher::new("E8 ?? ?? ?? ?? C3").unwrap();
+        let haystack = b"\xe8\x01\x02\x03\xc3\xe8\x01\x02\x03\x04\xc3";
+        assert_eq!(searcher.find_in(haystack), Some(5));
+        assert!(!searcher.search_in(&haystack[..10]));
+
+        let searcher = HexSearcher::new("4? ?5").unwrap();
+        for high in 0..=255u8 {
+            for low in [0x05, 0x15, 0xf5, 0x06] {
+                let expected = high >> 4 == 4 && low & 0xf == 5;
+                assert_eq!(searcher.search_in(&[high, low]), expected);
+            }
+        }
+
+        let searcher = HexSearcher::new("?? ??").unwrap();
+        assert_eq!(searcher.find_in(b"a"), None);
+        assert_eq!(searcher.find_in(b"ab"), Some(0));
+    }
+
+    #[test]
+    fn hex_alternatives() {
+        let searcher = HexSearcher::new("01 (02 | 03 04 | (05 | 06) ??) 07").unwrap();
+
+        for (haystack, expected) in [
+            (&b"\x00\x01\x02\x07"[..], Some(1)),
+            (b"\x00\x01\x03\x04\x07", Some(1)),
+            (b"\x00\x01\x05\xff\x07", Some(1)),
+            (b"\x00\x01\x06\x06\x07", Some(1)),
+            (b"\x01\x03\x07\x01\x02\x07", Some(3)),
+            (b"\x01\x05\x07", None),
+            (b"\x01\x04\x07", None),
+        ] {
+            assert_eq!(searcher.find_in(haystack), expected, "{haystack:?}");
+            assert_eq!(searcher.search_in(haystack), expected.is_some());
+        }
+
+        // The leftmost match is found whichever alternative it uses.
+        let searcher = HexSearcher::new("(AA BB CC DD | BB)").unwrap();
+        assert_eq!(searcher.find_in(b"\xaa\xbb\xcc\xdd"), Some(0));
+        assert_eq!(searcher.find_in(b"\xaa\xbb\xcc\xde"), Some(1));
+
+        // A later occurrence of the anchor may belong to an earlier match.
+        let searcher = HexSearcher::new("(01 02 03 | 02) FF").unwrap();
+        assert_eq!(
+            searcher.find_in(b"\x01\x02\x02\xff\x01\x02\x03\xff"),
+            Some(2)
+        );
+        assert_eq!(searcher.find_in(b"\x01\x02\x03\xff\x02\xff"), Some(0));
+        assert_eq!(searcher.find_in(b"\x02\x01\x02\x03\xff"), Some(1));
+    }
+
+    #[test]
+    fn hex_many_alternatives() {
+        // Alternatives are tried at once instead of one combination at a time.
+        let pattern = "(00 | 01) ".repeat(16);
+        let searcher = HexSearcher::new(&pattern).unwrap();
+
+        let mut haystack = [0x02; 64];
+        haystack[20..36]
+            .copy_from_slice(b"\x01\x00\x01\x01\x00\x00\x01\x00\x01\x01\x01\x00\x00\x01\x00\x01");
+        assert_eq!(searcher.find_in(&haystack), Some(20));
+        haystack[30] = 0x02;
+        assert_eq!(searcher.find_in(&haystack), None);
+
+        let searcher = HexSearcher::new(&format!("DE AD {pattern} BE EF")).unwrap();
+        let mut haystack = b"\xde\xad".repeat(2);
+        haystack.extend_from_slice(&[1; 16]);
+        haystack.extend_from_slice(b"\xbe\xef");
+        assert_eq!(searcher.find_in(&haystack), Some(2));
+        assert_eq!(searcher.find_in(&haystack[..haystack.len() - 1]), None);
+    }
+
+    #[test]
+    fn hex_invalid() {
+        for (pattern, column, kind) in [
+            ("DE AD G0", 7, HexErrorKind::InvalidCharacter),
+            ("DE AD 0G", 8, HexErrorKind::InvalidCharacter),
+            ("DE AD | BE", 7, HexErrorKind::InvalidCharacter),
+            ("DE AD) BE", 6, HexErrorKind::InvalidCharacter),
+            ("DE AD é", 7, HexErrorKind::InvalidCharacter),
+            ("é DE", 1, HexErrorKind::InvalidCharacter),
+            ("DE A", 4, HexErrorKind::IncompleteByte),
+            ("DE A BE", 4, HexErrorKind::IncompleteByte),
+            ("DE (A)", 5, HexErrorKind::IncompleteByte),
+            ("DE (AD | BE", 4, HexErrorKind::UnclosedAlternative),
+            ("DE (AD | ", 4, HexErrorKind::UnclosedAlternative),
+            ("DE (AD", 4, HexErrorKind::UnclosedAlternative),
+            ("DE (AD | )", 10, HexErrorKind::EmptyAlternative),
+            ("DE (| AD)", 5, HexErrorKind::EmptyAlternative),
+            ("DE ()", 5, HexErrorKind::EmptyAlternative),
+            (
+                &"(00 | 01) ".repeat(17),
+                161,
+                HexErrorKind::TooManyAlternatives,
+            ),
+            (
+                &format!("DE ({})", "(00 | 01) ".repeat(17)),
+                165,
+                HexErrorKind::TooManyAlternatives,
+            ),
+        ] {
+            assert_eq!(
+                HexSearcher::new(pattern).unwrap_err(),
+                HexError { column, kind },
+                "{pattern}"
+            );
+        }
+    }
+}
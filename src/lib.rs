@@ -3,9 +3,22 @@
 //! Muła](http://0x80.pl/articles/simd-strfind.html). For a fast multi-pattern
 //! substring search algorithm, see instead the [`aho-corasick`
 //! crate](https://github.com/BurntSushi/aho-corasick).
+//!
+//! # Crate features
+//!
+//! The `std` feature is enabled by default. Without it, the crate is
+//! `no_std` and `Finder` only uses SIMD instructions enabled at compile time
+//! with `target_feature`, since CPU features cannot be detected at runtime. The
+//! `alloc` feature, implied by `std`, provides the `Needle` implementations
+//! for `Box`, `Rc`, `Arc`, `Vec`, `String` and `Cow`, as well as
+//! `LikeMatcher`, `GlobMatcher`, `HexSearcher`, `SequenceSearcher`, `Query`
+//! and `StreamingMatcher`. The `rayon` feature provides methods of `Finder`
+//! searching large haystacks in parallel.
 
 #![warn(missing_docs)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![cfg_attr(feature = "stdsimd", feature(portable_simd))]
+#![cfg_attr(feature = "pattern", feature(pattern))]
 
 /// Substring search implementations using aarch64 architecture features.
 #[cfg(all(target_arch = "aarch64", feature = "aarch64"))]
@@ -23,12 +36,114 @@ pub mod x86;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm32;
 
+pub mod memmem;
+
+/// Integration of `Finder` with the string methods of the standard library.
+#[cfg(feature = "pattern")]
+pub mod pattern;
+
 mod bits;
+mod class;
+mod columnar;
+mod finder;
+#[cfg(feature = "alloc")]
+mod glob;
+#[cfg(feature = "alloc")]
+mod hex;
+#[cfg(feature = "alloc")]
+mod like;
 mod memcmp;
+mod near;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod prefilter;
+#[cfg(feature = "alloc")]
+mod query;
+mod rank;
+#[cfg(feature = "alloc")]
+mod sequence;
+#[cfg(feature = "alloc")]
+mod stream;
+mod value;
+#[cfg(feature = "alloc")]
+mod wildcard;
+
+pub use class::ByteClass;
+pub use columnar::Offset;
+pub use finder::{Finder, MatchIndices, SplitStr};
+#[cfg(feature = "alloc")]
+pub use glob::{GlobError, GlobMatcher};
+#[cfg(feature = "alloc")]
+pub use hex::{HexError, HexSearcher};
+#[cfg(feature = "alloc")]
+pub use like::{LikeError, LikeMatcher};
+pub use near::FindNear;
+pub use prefilter::{Prefilter, PrefilterState};
+#[cfg(feature = "alloc")]
+pub use query::{Query, QueryError};
+#[cfg(feature = "alloc")]
+pub use sequence::SequenceSearcher;
+#[cfg(feature = "macros")]
+pub use sliceslice_macros::searcher;
+#[cfg(feature = "alloc")]
+pub use stream::{Feed, StreamingMatcher};
+pub use value::{find_aligned_value, find_value, Endianness, Value};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::rank::rarest_position;
+    use crate::{sealed, Needle};
+
+    /// Needle literal generated by the `searcher!` macro, which is not part of
+    /// the public API.
+    ///
+    /// # Safety
+    ///
+    /// `eq_tail` must return either `None` or whether all but the first byte
+    /// of `BYTES` are equal to the `BYTES.len() - 1` bytes at `chunk`.
+    pub unsafe trait LiteralNeedle {
+        /// Bytes of the needle.
+        const BYTES: &'static [u8];
+
+        /// Compares all but the first byte of the needle with the bytes at
+        /// `chunk`, which must be valid for reads of `BYTES.len() - 1` bytes,
+        /// returning `None` to use the default comparison.
+        unsafe fn eq_tail(chunk: *const u8) -> Option<bool>;
+    }
 
-use memchr::memchr;
-use std::rc::Rc;
-use std::sync::Arc;
+    /// Needle wrapping a `LiteralNeedle`, whose candidates are verified using
+    /// its comparison.
+    #[derive(Clone, Copy, Debug)]
+    pub struct Literal<L>(pub L);
+
+    impl<L: LiteralNeedle> Needle for Literal<L> {
+        const SIZE: Option<usize> = Some(L::BYTES.len());
+
+        #[inline(always)]
+        fn as_bytes(&self) -> &[u8] {
+            L::BYTES
+        }
+
+        #[inline(always)]
+        unsafe fn eq_tail(&self, _: sealed::Token, chunk: *const u8) -> Option<bool> {
+            L::eq_tail(chunk)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "serde")]
+use alloc::format;
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, boxed::Box, rc::Rc, string::String, sync::Arc, vec::Vec};
+use core::{fmt, ops::Range};
+use memchr::{memchr, memrchr};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::error::Error;
 
 /// Needle that can be searched for within a haystack. It allows specialized
 /// searcher implementations for needle sizes known at compile time.
@@ -38,6 +153,27 @@ pub trait Needle {
     const SIZE: Option<usize>;
     /// Return the slice corresponding to the needle.
     fn as_bytes(&self) -> &[u8];
+
+    /// Compares all but the first byte of the needle with the bytes at `chunk`,
+    /// returning `None` to use the default comparison. The `sealed::Token`
+    /// argument prevents it from being called or overridden outside of this
+    /// crate: only `__private::Literal` does, for the `searcher!` macro.
+    #[doc(hidden)]
+    #[inline(always)]
+    unsafe fn eq_tail(&self, _: sealed::Token, _chunk: *const u8) -> Option<bool> {
+        None
+    }
+}
+
+mod sealed {
+    /// Argument of the methods of public traits which must not be called or
+    /// overridden outside of this crate.
+    #[derive(Clone, Copy)]
+    pub struct Token;
+
+    /// Supertrait of the public traits which must not be implemented outside
+    /// of this crate.
+    pub trait Sealed {}
 }
 
 impl<const N: usize> Needle for [u8; N] {
@@ -58,6 +194,7 @@ impl Needle for [u8] {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<N: Needle + ?Sized> Needle for Box<N> {
     const SIZE: Option<usize> = N::SIZE;
 
@@ -65,8 +202,14 @@ impl<N: Needle + ?Sized> Needle for Box<N> {
     fn as_bytes(&self) -> &[u8] {
         (**self).as_bytes()
     }
+
+    #[inline(always)]
+    unsafe fn eq_tail(&self, token: sealed::Token, chunk: *const u8) -> Option<bool> {
+        (**self).eq_tail(token, chunk)
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<N: Needle + ?Sized> Needle for Rc<N> {
     const SIZE: Option<usize> = N::SIZE;
 
@@ -74,8 +217,14 @@ impl<N: Needle + ?Sized> Needle for Rc<N> {
     fn as_bytes(&self) -> &[u8] {
         (**self).as_bytes()
     }
+
+    #[inline(always)]
+    unsafe fn eq_tail(&self, token: sealed::Token, chunk: *const u8) -> Option<bool> {
+        (**self).eq_tail(token, chunk)
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<N: Needle + ?Sized> Needle for Arc<N> {
     const SIZE: Option<usize> = N::SIZE;
 
@@ -83,6 +232,11 @@ impl<N: Needle + ?Sized> Needle for Arc<N> {
     fn as_bytes(&self) -> &[u8] {
         (**self).as_bytes()
     }
+
+    #[inline(always)]
+    unsafe fn eq_tail(&self, token: sealed::Token, chunk: *const u8) -> Option<bool> {
+        (**self).eq_tail(token, chunk)
+    }
 }
 
 impl<N: Needle + ?Sized> Needle for &N {
@@ -92,8 +246,14 @@ impl<N: Needle + ?Sized> Needle for &N {
     fn as_bytes(&self) -> &[u8] {
         (*self).as_bytes()
     }
+
+    #[inline(always)]
+    unsafe fn eq_tail(&self, token: sealed::Token, chunk: *const u8) -> Option<bool> {
+        (*self).eq_tail(token, chunk)
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl Needle for Vec<u8> {
     const SIZE: Option<usize> = None;
 
@@ -103,6 +263,35 @@ impl Needle for Vec<u8> {
     }
 }
 
+impl Needle for str {
+    const SIZE: Option<usize> = None;
+
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Needle for String {
+    const SIZE: Option<usize> = None;
+
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        String::as_bytes(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Needle for Cow<'_, str> {
+    const SIZE: Option<usize> = None;
+
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+}
+
 trait NeedleWithSize: Needle {
     #[inline]
     fn size(&self) -> usize {
@@ -112,14 +301,98 @@ trait NeedleWithSize: Needle {
             self.as_bytes().len()
         }
     }
+}
 
-    #[inline]
-    fn is_empty(&self) -> bool {
-        self.size() == 0
+impl<N: Needle + ?Sized> NeedleWithSize for N {}
+
+/// Backend-neutral description of a searcher, made of the needle and the
+/// `position` of the second byte used by the SIMD hash.
+///
+/// Choosing a good `position` can be expensive, so it may be done once ahead
+/// of time and the result shipped to wherever the search runs. With the `serde`
+/// feature enabled, `CompiledNeedle` can be serialized and deserialized, and
+/// any searcher can then be created from it using `from_compiled`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "UncheckedCompiledNeedle<N>",
+        bound(deserialize = "N: Deserialize<'de>")
+    )
+)]
+pub struct CompiledNeedle<N: Needle> {
+    needle: N,
+    position: usize,
+}
+
+impl<N: Needle> CompiledNeedle<N> {
+    /// Creates a new compiled needle for `needle`. By default, `position` is
+    /// set to the last character in the needle.
+    pub fn new(needle: N) -> Self {
+        // Wrapping prevents panicking on unsigned integer underflow when
+        // `needle` is empty.
+        let position = needle.as_bytes().len().wrapping_sub(1);
+        Self::with_position(needle, position)
+    }
+
+    /// Same as `new` but allows additionally specifying the `position` to use.
+    ///
+    /// # Panics
+    ///
+    /// When `needle` is not empty, panics if `position` is not a valid index
+    /// for `needle`.
+    pub fn with_position(needle: N, position: usize) -> Self {
+        let size = needle.as_bytes().len();
+        assert!(size == 0 || position < size);
+
+        Self { needle, position }
+    }
+
+    /// Returns the needle.
+    pub fn needle(&self) -> &N {
+        &self.needle
+    }
+
+    /// Returns the `position` of the second byte used by the SIMD hash.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the needle and its `position`.
+    pub fn into_parts(self) -> (N, usize) {
+        (self.needle, self.position)
     }
 }
 
-impl<N: Needle + ?Sized> NeedleWithSize for N {}
+/// Serialized form of `CompiledNeedle`, whose `position` is checked before
+/// deserialization completes.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct UncheckedCompiledNeedle<N> {
+    needle: N,
+    position: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<N: Needle> TryFrom<UncheckedCompiledNeedle<N>> for CompiledNeedle<N> {
+    type Error = String;
+
+    fn try_from(unchecked: UncheckedCompiledNeedle<N>) -> Result<Self, Self::Error> {
+        let size = unchecked.needle.as_bytes().len();
+        if size != 0 && unchecked.position >= size {
+            return Err(format!(
+                "position {} is out of bounds for a needle of length {}",
+                unchecked.position, size
+            ));
+        }
+
+        Ok(Self {
+            needle: unchecked.needle,
+            position: unchecked.position,
+        })
+    }
+}
 
 /// Single-byte searcher using `memchr` for faster matching.
 pub struct MemchrSearcher(u8);
@@ -144,6 +417,347 @@ impl MemchrSearcher {
     pub fn search_in(&self, haystack: &[u8]) -> bool {
         self.inlined_search_in(haystack)
     }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack`, if any.
+    pub fn find_in(&self, haystack: &[u8]) -> Option<usize> {
+        memchr(self.0, haystack)
+    }
+
+    /// Returns the offset of the last occurrence of the `needle` within
+    /// `haystack`, if any.
+    pub fn rfind_in(&self, haystack: &[u8]) -> Option<usize> {
+        memrchr(self.0, haystack)
+    }
+}
+
+/// Error returned by `search_in_with_budget` when the maximum number of
+/// candidate verifications is reached before the search completes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BudgetExceeded {
+    offset: usize,
+}
+
+impl BudgetExceeded {
+    /// Returns the offset in the haystack at which the search stopped. The
+    /// needle is guaranteed not to start at any offset before this one.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "verification budget exceeded at offset {} of the haystack",
+            self.offset
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for BudgetExceeded {}
+
+/// Outcome of scanning a haystack for a needle.
+enum Scan {
+    /// The needle occurs at the given offset, which is its first occurrence.
+    Found(usize),
+    /// The needle does not occur in the haystack.
+    NotFound,
+    /// The verification budget ran out before the scan completed. The needle
+    /// does not occur at any offset before the one given.
+    Exhausted(usize),
+}
+
+impl Scan {
+    #[inline]
+    fn offset_by(self, offset: usize) -> Self {
+        match self {
+            Self::Found(position) => Self::Found(offset + position),
+            Self::NotFound => Self::NotFound,
+            Self::Exhausted(position) => Self::Exhausted(offset + position),
+        }
+    }
+
+    #[inline]
+    fn is_found(&self) -> bool {
+        matches!(self, Self::Found(_))
+    }
+
+    /// Returns the offset of the first candidate reported by a scan performed
+    /// without any verification budget. Haystacks no longer than the needle
+    /// must be checked with `Searcher::short_candidate` instead, since the scan
+    /// compares them directly.
+    #[inline]
+    fn into_candidate(self) -> Option<usize> {
+        match self {
+            Self::Found(offset) | Self::Exhausted(offset) => Some(offset),
+            Self::NotFound => None,
+        }
+    }
+
+    /// Completes a scan performed with a `linear_budget`, using the linear-time
+    /// Two-Way implementation of `memchr::memmem` to search the remainder of
+    /// `haystack` if the budget ran out.
+    #[inline]
+    fn finish_linear(self, haystack: &[u8], needle: &[u8]) -> bool {
+        match self {
+            Self::Found(_) => true,
+            Self::NotFound => false,
+            Self::Exhausted(offset) => memchr::memmem::find(&haystack[offset..], needle).is_some(),
+        }
+    }
+}
+
+impl From<Scan> for Option<usize> {
+    #[inline]
+    fn from(scan: Scan) -> Self {
+        match scan {
+            Scan::Found(position) => Some(position),
+            Scan::NotFound => None,
+            Scan::Exhausted(_) => unreachable!(),
+        }
+    }
+}
+
+impl From<Scan> for Result<bool, BudgetExceeded> {
+    #[inline]
+    fn from(scan: Scan) -> Self {
+        match scan {
+            Scan::Found(_) => Ok(true),
+            Scan::NotFound => Ok(false),
+            Scan::Exhausted(offset) => Err(BudgetExceeded { offset }),
+        }
+    }
+}
+
+/// Limit on the number of candidate verifications performed by a scan.
+trait Budget {
+    /// Consumes one verification, returning `false` if none are left.
+    fn spend(&mut self) -> bool;
+}
+
+/// Budget for regular searches, which never runs out.
+struct Unlimited;
+
+impl Budget for Unlimited {
+    #[inline(always)]
+    fn spend(&mut self) -> bool {
+        true
+    }
+}
+
+impl Budget for usize {
+    #[inline(always)]
+    fn spend(&mut self) -> bool {
+        if *self == 0 {
+            return false;
+        }
+
+        *self -= 1;
+        true
+    }
+}
+
+/// Restriction on the occurrences of the needle reported by a scan, applied
+/// independently of its verification budget.
+trait Filter {
+    /// Returns the stride of the offsets at which occurrences may start.
+    /// Candidates at other offsets are masked out before verification.
+    #[inline(always)]
+    fn stride(&self) -> usize {
+        1
+    }
+
+    /// Returns `true` if the occurrence of the needle found at `candidate` is
+    /// to be reported, or `false` for the scan to carry on past it.
+    #[inline(always)]
+    unsafe fn accept(&self, _candidate: *const u8) -> bool {
+        true
+    }
+}
+
+/// Filter for regular searches, which reports every occurrence.
+struct Unfiltered;
+
+impl Filter for Unfiltered {}
+
+/// Filter accepting only the occurrences of the needle which are whole words,
+/// i.e. which are neither preceded nor followed by a byte of `class` in
+/// `haystack`.
+struct WordBoundary<'a> {
+    haystack: &'a [u8],
+    size: usize,
+    class: &'a ByteClass,
+}
+
+impl<'a> WordBoundary<'a> {
+    #[inline]
+    fn new(haystack: &'a [u8], size: usize, class: &'a ByteClass) -> Self {
+        Self {
+            haystack,
+            size,
+            class,
+        }
+    }
+}
+
+impl Filter for WordBoundary<'_> {
+    #[inline(always)]
+    unsafe fn accept(&self, candidate: *const u8) -> bool {
+        let offset = candidate.offset_from(self.haystack.as_ptr()) as usize;
+        is_whole_word(self.haystack, offset, self.size, self.class)
+    }
+}
+
+/// Filter accepting only the occurrences of the needle at offsets which are
+/// multiples of `stride`.
+struct Aligned {
+    stride: usize,
+}
+
+impl Aligned {
+    #[inline]
+    fn new(stride: usize) -> Self {
+        assert_ne!(stride, 0, "stride must not be zero");
+        Self { stride }
+    }
+}
+
+impl Filter for Aligned {
+    #[inline(always)]
+    fn stride(&self) -> usize {
+        self.stride
+    }
+}
+
+/// Returns a mask of the lanes of a chunk loaded at `offset` which correspond
+/// to multiples of `stride`, given the `pattern` of lanes which would be set
+/// for an `offset` of zero.
+#[inline]
+fn alignment_mask(pattern: u32, stride: usize, offset: usize) -> u32 {
+    let shift = (stride - offset % stride) % stride;
+    pattern.checked_shl(shift as u32).unwrap_or(0)
+}
+
+/// Returns the offset of the first occurrence found by `find` in `haystack`
+/// at a multiple of `stride`, calling `find` again from the next multiple
+/// past each rejected occurrence. Used where occurrences cannot be masked by
+/// a SIMD scan.
+#[inline]
+fn find_aligned_with<F: FnMut(&[u8]) -> Option<usize>>(
+    haystack: &[u8],
+    stride: usize,
+    mut find: F,
+) -> Option<usize> {
+    assert_ne!(stride, 0, "stride must not be zero");
+
+    let mut position = 0;
+    loop {
+        let offset = position + find(haystack.get(position..)?)?;
+        if offset % stride == 0 {
+            return Some(offset);
+        }
+
+        position = (offset / stride + 1).checked_mul(stride)?;
+    }
+}
+
+/// Returns `true` if the `size` bytes at `offset` in `haystack` are neither
+/// preceded nor followed by a byte of `class`.
+#[inline]
+fn is_whole_word(haystack: &[u8], offset: usize, size: usize, class: &ByteClass) -> bool {
+    let before = offset.checked_sub(1).map(|i| haystack[i]);
+    let after = haystack.get(offset + size).copied();
+
+    !before.is_some_and(|byte| class.contains(byte))
+        && !after.is_some_and(|byte| class.contains(byte))
+}
+
+/// Returns the offset of the first occurrence found by `find` in `haystack`
+/// at or after `start` which is a whole word according to `class`, calling
+/// `find` again past each rejected occurrence. Used where occurrences cannot be
+/// checked by a SIMD scan.
+///
+/// # Panics
+///
+/// Panics if `start` is greater than the length of `haystack`.
+#[inline]
+fn find_word_with<F: FnMut(&[u8]) -> Option<usize>>(
+    haystack: &[u8],
+    start: usize,
+    size: usize,
+    class: &ByteClass,
+    mut find: F,
+) -> Option<usize> {
+    // Only check that `start` is in bounds.
+    let _ = &haystack[start..];
+
+    let mut position = start;
+    loop {
+        let offset = position + find(haystack.get(position..)?)?;
+        if is_whole_word(haystack, offset, size, class) {
+            return Some(offset);
+        }
+
+        position = offset + 1;
+    }
+}
+
+/// Number of candidate verifications allowed before a linear search falls back
+/// to `memchr::memmem`. Each verification compares at most `needle_size` bytes,
+/// so the SIMD scan performs at most `haystack_size` bytes of verification work
+/// in total, keeping the overall running time linear.
+#[inline]
+fn linear_budget(haystack_size: usize, needle_size: usize) -> usize {
+    haystack_size / needle_size
+}
+
+/// Writes to `out` whether each of `haystacks` contains the needle, using
+/// `search`. Meant to be inlined in the batch searches of each searcher, so that
+/// the searcher is dispatched once for the whole batch.
+///
+/// # Panics
+///
+/// Panics if `haystacks` and `out` have different lengths.
+#[inline(always)]
+fn search_in_batch_with<F: FnMut(&[u8]) -> bool>(
+    haystacks: &[&[u8]],
+    out: &mut [bool],
+    mut search: F,
+) {
+    assert_eq!(haystacks.len(), out.len(), "output length mismatch");
+
+    for (haystack, out) in haystacks.iter().zip(out) {
+        *out = search(haystack);
+    }
+}
+
+/// Same as `search_in_batch_with` but sets bit `i % 64` of `out[i / 64]` if
+/// and only if `haystacks[i]` contains the needle.
+///
+/// # Panics
+///
+/// Panics if `out` does not have exactly one word for every 64 haystacks,
+/// rounded up.
+#[inline(always)]
+fn search_in_batch_bits_with<F: FnMut(&[u8]) -> bool>(
+    haystacks: &[&[u8]],
+    out: &mut [u64],
+    mut search: F,
+) {
+    assert_eq!(
+        haystacks.len().div_ceil(u64::BITS as usize),
+        out.len(),
+        "output length mismatch"
+    );
+
+    for (haystacks, out) in haystacks.chunks(u64::BITS as usize).zip(out) {
+        *out = haystacks.iter().enumerate().fold(0, |bits, (i, haystack)| {
+            bits | (search(haystack) as u64) << i
+        });
+    }
 }
 
 /// Represents a generic SIMD register type.
@@ -197,6 +811,19 @@ trait Searcher<N: NeedleWithSize + ?Sized> {
 
     fn position(&self) -> usize;
 
+    /// Returns the only candidate in a `haystack` no longer than the needle,
+    /// judging from the same two bytes as the SIMD hash.
+    #[inline]
+    fn short_candidate(&self, haystack: &[u8]) -> Option<usize> {
+        let needle = self.needle().as_bytes();
+        let position = self.position();
+
+        (haystack.len() == needle.len()
+            && haystack[0] == needle[0]
+            && haystack[position] == needle[position])
+            .then_some(0)
+    }
+
     #[multiversion::multiversion]
     #[clone(target = "[x86|x86_64]+avx2")]
     #[clone(target = "wasm32+simd128")]
@@ -204,12 +831,12 @@ trait Searcher<N: NeedleWithSize + ?Sized> {
         all(target_arch = "aarch64", feature = "aarch64"),
         clone(target = "aarch64+neon")
     )]
-    unsafe fn vector_search_in_chunk<V: Vector>(
+    unsafe fn vector_candidates_in_chunk<V: Vector>(
         &self,
         hash: &VectorHash<V>,
         start: *const u8,
         mask: u32,
-    ) -> bool {
+    ) -> u32 {
         let first = V::load(start);
         let last = V::load(start.add(self.position()));
 
@@ -217,14 +844,25 @@ trait Searcher<N: NeedleWithSize + ?Sized> {
         let eq_last = V::lanes_eq(hash.last, last);
 
         let eq = V::bitwise_and(eq_first, eq_last);
-        let mut eq = V::to_bitmask(eq) & mask;
+        V::to_bitmask(eq) & mask
+    }
 
-        let chunk = start.add(1);
+    /// Compares the needle with the haystack at `candidate`, whose first byte
+    /// is already known to match.
+    #[multiversion::multiversion]
+    #[clone(target = "[x86|x86_64]+avx2")]
+    #[clone(target = "wasm32+simd128")]
+    #[cfg_attr(
+        all(target_arch = "aarch64", feature = "aarch64"),
+        clone(target = "aarch64+neon")
+    )]
+    unsafe fn verify_candidate(&self, candidate: *const u8) -> bool {
+        let chunk = candidate.add(1);
         let needle = self.needle().as_bytes().as_ptr().add(1);
 
-        while eq != 0 {
-            let chunk = chunk.add(eq.trailing_zeros() as usize);
-            let equal = match N::SIZE {
+        match self.needle().eq_tail(sealed::Token, chunk) {
+            Some(equal) => equal,
+            None => match N::SIZE {
                 Some(0) => unreachable!(),
                 Some(1) => dispatch!(memcmp::specialized::<0>(chunk, needle)),
                 Some(2) => dispatch!(memcmp::specialized::<1>(chunk, needle)),
@@ -243,15 +881,43 @@ trait Searcher<N: NeedleWithSize + ?Sized> {
                 Some(15) => dispatch!(memcmp::specialized::<14>(chunk, needle)),
                 Some(16) => dispatch!(memcmp::specialized::<15>(chunk, needle)),
                 _ => dispatch!(memcmp::generic(chunk, needle, self.needle().size() - 1)),
-            };
-            if equal {
-                return true;
+            },
+        }
+    }
+
+    #[multiversion::multiversion]
+    #[clone(target = "[x86|x86_64]+avx2")]
+    #[clone(target = "wasm32+simd128")]
+    #[cfg_attr(
+        all(target_arch = "aarch64", feature = "aarch64"),
+        clone(target = "aarch64+neon")
+    )]
+    unsafe fn vector_search_in_chunk<V: Vector, B: Budget, F: Filter>(
+        &self,
+        hash: &VectorHash<V>,
+        start: *const u8,
+        mask: u32,
+        budget: &mut B,
+        filter: &F,
+    ) -> Scan {
+        let mut eq = dispatch!(self.vector_candidates_in_chunk(hash, start, mask));
+
+        // Offsets are relative to `start` and adjusted by the caller.
+        while eq != 0 {
+            let offset = eq.trailing_zeros() as usize;
+            if !budget.spend() {
+                return Scan::Exhausted(offset);
+            }
+
+            let candidate = start.add(offset);
+            if dispatch!(self.verify_candidate(candidate)) && filter.accept(candidate) {
+                return Scan::Found(offset);
             }
 
             eq = dispatch!(bits::clear_leftmost_set(eq));
         }
 
-        false
+        Scan::NotFound
     }
 
     #[multiversion::multiversion]
@@ -261,38 +927,135 @@ trait Searcher<N: NeedleWithSize + ?Sized> {
         all(target_arch = "aarch64", feature = "aarch64"),
         clone(target = "aarch64+neon")
     )]
-    unsafe fn vector_search_in<V: Vector>(
+    unsafe fn vector_search_in<V: Vector, B: Budget, F: Filter>(
         &self,
         haystack: &[u8],
+        positions: Range<usize>,
         end: usize,
         hash: &VectorHash<V>,
-    ) -> bool {
+        budget: &mut B,
+        filter: &F,
+    ) -> Scan {
         debug_assert!(haystack.len() >= self.needle().size());
+        let (start, limit) = (positions.start, positions.end);
+        debug_assert!(start < limit && limit <= end);
+
+        // Candidates at unaligned offsets are masked out along with the lanes
+        // outside of the haystack.
+        let stride = filter.stride();
+        let pattern = match stride {
+            1 => u32::MAX,
+            _ => (0..u32::BITS as usize)
+                .step_by(stride)
+                .fold(0, |pattern, lane| pattern | 1 << lane),
+        };
+        let aligned = |mask: u32, offset: usize| match stride {
+            1 => mask,
+            _ => mask & alignment_mask(pattern, stride, offset),
+        };
 
-        let mut chunks = haystack[..end].chunks_exact(V::LANES);
-        for chunk in &mut chunks {
-            if dispatch!(self.vector_search_in_chunk(hash, chunk.as_ptr(), u32::MAX)) {
-                return true;
+        // Only the positions from `start` to `limit` may be reported, but
+        // chunks may be loaded anywhere before `end` so the last one can stay a
+        // full vector.
+        let mut chunks = haystack[start..limit].chunks_exact(V::LANES);
+        for (i, chunk) in (&mut chunks).enumerate() {
+            let offset = start + i * V::LANES;
+            let mask = aligned(u32::MAX, offset);
+            match dispatch!(self.vector_search_in_chunk(hash, chunk.as_ptr(), mask, budget, filter))
+            {
+                Scan::NotFound => {}
+                scan => return scan.offset_by(offset),
             }
         }
 
+        let remainder = chunks.remainder().len();
+        if remainder > 0 {
+            let offset = (limit - remainder).min(end - V::LANES);
+            let chunk = haystack.as_ptr().add(offset);
+            let low = limit - remainder - offset;
+            let high = limit - offset;
+            let mask = (u32::MAX >> (u32::BITS as usize - high)) & (u32::MAX << low);
+            let mask = aligned(mask, offset);
+
+            match dispatch!(self.vector_search_in_chunk(hash, chunk, mask, budget, filter)) {
+                Scan::NotFound => {}
+                scan => return scan.offset_by(offset),
+            }
+        }
+
+        Scan::NotFound
+    }
+
+    #[multiversion::multiversion]
+    #[clone(target = "[x86|x86_64]+avx2")]
+    #[clone(target = "wasm32+simd128")]
+    #[cfg_attr(
+        all(target_arch = "aarch64", feature = "aarch64"),
+        clone(target = "aarch64+neon")
+    )]
+    unsafe fn vector_rsearch_in_chunk<V: Vector>(
+        &self,
+        hash: &VectorHash<V>,
+        start: *const u8,
+        mask: u32,
+    ) -> Option<usize> {
+        let mut eq = dispatch!(self.vector_candidates_in_chunk(hash, start, mask));
+
+        while eq != 0 {
+            let offset = (u32::BITS - 1 - eq.leading_zeros()) as usize;
+            if dispatch!(self.verify_candidate(start.add(offset))) {
+                return Some(offset);
+            }
+
+            eq = dispatch!(bits::clear_rightmost_set(eq));
+        }
+
+        None
+    }
+
+    /// Same as `vector_search_in` but returns the offset of the last occurrence
+    /// of the needle, scanning the haystack backwards.
+    #[multiversion::multiversion]
+    #[clone(target = "[x86|x86_64]+avx2")]
+    #[clone(target = "wasm32+simd128")]
+    #[cfg_attr(
+        all(target_arch = "aarch64", feature = "aarch64"),
+        clone(target = "aarch64+neon")
+    )]
+    unsafe fn vector_rsearch_in<V: Vector>(
+        &self,
+        haystack: &[u8],
+        end: usize,
+        hash: &VectorHash<V>,
+    ) -> Option<usize> {
+        debug_assert!(haystack.len() >= self.needle().size());
+
+        let chunks = haystack[..end].chunks_exact(V::LANES);
+
         let remainder = chunks.remainder().len();
         if remainder > 0 {
             let start = haystack.as_ptr().add(end - V::LANES);
             let mask = u32::MAX << (V::LANES - remainder);
 
-            if dispatch!(self.vector_search_in_chunk(hash, start, mask)) {
-                return true;
+            if let Some(offset) = dispatch!(self.vector_rsearch_in_chunk(hash, start, mask)) {
+                return Some(end - V::LANES + offset);
             }
         }
 
-        false
+        for (i, chunk) in chunks.enumerate().rev() {
+            let start = chunk.as_ptr();
+            if let Some(offset) = dispatch!(self.vector_rsearch_in_chunk(hash, start, u32::MAX)) {
+                return Some(i * V::LANES + offset);
+            }
+        }
+
+        None
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{MemchrSearcher, Needle};
+    use super::{CompiledNeedle, MemchrSearcher};
 
     fn memchr_search(haystack: &[u8], needle: &[u8]) -> bool {
         MemchrSearcher::new(needle[0]).search_in(haystack)
@@ -329,7 +1092,9 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "alloc")]
     fn needle_array_size() {
+        use super::Needle;
         use std::rc::Rc;
         use std::sync::Arc;
 
@@ -345,7 +1110,36 @@ mod tests {
     }
 
     #[test]
+    #[should_panic]
+    fn compiled_needle_invalid_position() {
+        CompiledNeedle::with_position(b"foo", 3);
+    }
+
+    #[test]
+    fn compiled_needle_empty() {
+        assert_eq!(CompiledNeedle::new(b"").into_parts(), (b"", usize::MAX));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn compiled_needle_serde() {
+        let compiled = CompiledNeedle::with_position(b"foo".to_vec(), 1);
+
+        let json = serde_json::to_string(&compiled).unwrap();
+        assert_eq!(json, r#"{"needle":[102,111,111],"position":1}"#);
+        assert_eq!(
+            serde_json::from_str::<CompiledNeedle<Vec<u8>>>(&json).unwrap(),
+            compiled
+        );
+
+        let json = r#"{"needle":[102,111,111],"position":3}"#;
+        assert!(serde_json::from_str::<CompiledNeedle<Vec<u8>>>(json).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
     fn needle_slice_size() {
+        use super::Needle;
         use std::rc::Rc;
         use std::sync::Arc;
 
@@ -363,21 +1157,89 @@ mod tests {
     pub(crate) trait TestSearcher {
         fn with_position(needle: &'static [u8], position: usize) -> Self;
         fn search_in(&self, haystack: &[u8]) -> bool;
+        fn search_in_linear(&self, haystack: &[u8]) -> bool;
+        fn search_in_with_budget(
+            &self,
+            haystack: &[u8],
+            max_verifications: usize,
+        ) -> Result<bool, super::BudgetExceeded>;
+        fn find_in(&self, haystack: &[u8]) -> Option<usize>;
+        fn rfind_in(&self, haystack: &[u8]) -> Option<usize>;
+        fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize>;
+        fn search_in_range(&self, haystack: &[u8], range: core::ops::Range<usize>) -> bool;
+        fn search_in_batch(&self, haystacks: &[&[u8]], out: &mut [bool]);
+        fn search_in_batch_bits(&self, haystacks: &[&[u8]], out: &mut [u64]);
     }
 
     fn search<S: TestSearcher>(haystack: &[u8], needle: &'static [u8]) -> bool {
-        let result = haystack
+        let first = haystack
             .windows(needle.len())
-            .any(|window| window == needle);
+            .position(|window| window == needle);
+        let last = haystack
+            .windows(needle.len())
+            .rposition(|window| window == needle);
+        let result = first.is_some();
+
+        // Range boundaries around the ends of the haystack and of the first
+        // occurrence, where partial chunks are loaded.
+        let mut boundaries = vec![0, 1, 2, haystack.len() / 3, haystack.len() / 2];
+        boundaries.extend((0..3).map(|i| haystack.len().saturating_sub(i)));
+        if let Some(first) = first {
+            let end = first + needle.len();
+            boundaries.extend([first, first + 1, end - 1, end, end + 1]);
+        }
+        boundaries.retain(|&boundary| boundary <= haystack.len());
+        boundaries.sort_unstable();
+        boundaries.dedup();
 
         for position in 0..needle.len() {
             let searcher = S::with_position(needle, position);
             assert_eq!(searcher.search_in(haystack), result);
+            assert_eq!(searcher.search_in_linear(haystack), result);
+            assert_eq!(
+                searcher.search_in_with_budget(haystack, usize::MAX),
+                Ok(result)
+            );
+            assert_eq!(searcher.find_in(haystack), first);
+            assert_eq!(searcher.rfind_in(haystack), last);
+
+            for &start in &boundaries {
+                let expected = haystack[start..]
+                    .windows(needle.len())
+                    .position(|window| window == needle)
+                    .map(|offset| start + offset);
+                assert_eq!(searcher.find_at(haystack, start), expected);
+
+                for &end in boundaries.iter().filter(|&&end| end >= start) {
+                    let expected = haystack[start..end]
+                        .windows(needle.len())
+                        .any(|window| window == needle);
+                    assert_eq!(
+                        searcher.search_in_range(haystack, start..end),
+                        expected,
+                        "{start}..{end}"
+                    );
+                }
+            }
+
+            let haystacks = [haystack, b"", haystack];
+            let mut out = [!result; 3];
+            searcher.search_in_batch(&haystacks, &mut out);
+            assert_eq!(out, [result, needle.is_empty(), result]);
+
+            let mut out = [u64::MAX];
+            searcher.search_in_batch_bits(&haystacks, &mut out);
+            assert_eq!(
+                out,
+                [result as u64 | (needle.is_empty() as u64) << 1 | (result as u64) << 2]
+            );
         }
 
         result
     }
 
+    /// Generates the common test suite for a searcher implementing
+    /// `TestSearcher`.
     #[macro_export]
     macro_rules! generate_tests {
         ($mod: ident, $name:ident) => {
@@ -413,6 +1275,11 @@ mod tests {
                 fn test_search_middle() {
                     $crate::tests::search_middle::<$name<&[u8]>>();
                 }
+
+                #[test]
+                fn test_search_pathological() {
+                    $crate::tests::search_pathological::<$name<&[u8]>>();
+                }
             }
         };
     }
@@ -540,4 +1407,14 @@ mod tests {
             b"Maecenas commodo posuere orci a consectetur"
         ));
     }
+
+    pub(crate) fn search_pathological<S: TestSearcher>() {
+        let needle = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaab";
+
+        let mut haystack = vec![b'a'; 4096];
+        assert!(!search::<S>(&haystack, needle));
+
+        haystack.extend_from_slice(needle);
+        assert!(search::<S>(&haystack, needle));
+    }
 }
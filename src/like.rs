@@ -0,0 +1,257 @@
+use crate::wildcard::{Token, WildcardMatcher};
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// Error returned when compiling a `LIKE` pattern which ends with its escape
+/// character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LikeError {
+    offset: usize,
+}
+
+impl LikeError {
+    /// Returns the offset in the pattern of the trailing escape character.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for LikeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LIKE pattern ends with an escape character at offset {}",
+            self.offset
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for LikeError {}
+
+/// Matcher for SQL `LIKE` and `ILIKE` patterns over bytes.
+///
+/// In a pattern, `%` matches any sequence of bytes, including an empty one,
+/// and `_` matches exactly one byte, which is only a character for ASCII
+/// haystacks. An optional escape character makes the byte following it match
+/// literally. With `ilike`, ASCII letters match regardless of their case.
+///
+/// The pattern is split at each `%` into literal segments. The first and last
+/// segments are checked at the start and end of the haystack, while the others
+/// are looked for in order in between, using a `Finder` for their longest run
+/// of literal bytes and verifying the whole segment around each occurrence.
+///
+/// # Example
+///
+/// ```
+/// use sliceslice::LikeMatcher;
+///
+/// let matcher = LikeMatcher::like(b"%ipsum _olor%", None).unwrap();
+/// assert!(matcher.matches(b"Lorem ipsum dolor sit amet"));
+/// assert!(!matcher.matches(b"Lorem ipsum, dolor sit amet"));
+///
+/// let matcher = LikeMatcher::ilike(b"100\\%%", Some(b'\\')).unwrap();
+/// assert!(matcher.matches(b"100% CORRECT"));
+/// assert!(!matcher.matches(b"1000"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LikeMatcher {
+    matcher: WildcardMatcher,
+}
+
+impl LikeMatcher {
+    /// Compiles the `LIKE` `pattern`, where `escape` is the optional escape
+    /// character.
+    pub fn like(pattern: &[u8], escape: Option<u8>) -> Result<Self, LikeError> {
+        Self::new(pattern, escape, false)
+    }
+
+    /// Compiles the `ILIKE` `pattern`, same as `like` but matching ASCII
+    /// letters regardless of their case.
+    pub fn ilike(pattern: &[u8], escape: Option<u8>) -> Result<Self, LikeError> {
+        Self::new(pattern, escape, true)
+    }
+
+    fn new(pattern: &[u8], escape: Option<u8>, case_insensitive: bool) -> Result<Self, LikeError> {
+        let literal = |byte: u8| match case_insensitive {
+            true => Token::Byte(byte.to_ascii_lowercase()),
+            false => Token::Byte(byte),
+        };
+
+        let mut segments = Vec::new();
+        let mut segment = Vec::new();
+
+        let mut bytes = pattern.iter().copied().enumerate();
+        while let Some((offset, byte)) = bytes.next() {
+            match byte {
+                _ if Some(byte) == escape => match bytes.next() {
+                    Some((_, byte)) => segment.push(literal(byte)),
+                    None => return Err(LikeError { offset }),
+                },
+                b'%' => segments.push(core::mem::take(&mut segment)),
+                b'_' => segment.push(Token::Any),
+                _ => segment.push(literal(byte)),
+            }
+        }
+        segments.push(segment);
+
+        Ok(Self {
+            matcher: WildcardMatcher::new(segments, case_insensitive),
+        })
+    }
+
+    /// Returns `true` if the whole `haystack` matches the pattern.
+    pub fn matches(&self, haystack: &[u8]) -> bool {
+        self.matcher.matches(haystack)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LikeError, LikeMatcher};
+
+    /// Naive backtracking implementation of `LIKE`, without escaping.
+    fn naive(pattern: &[u8], haystack: &[u8], case_insensitive: bool) -> bool {
+        match pattern.split_first() {
+            None => haystack.is_empty(),
+            Some((b'%', rest)) => {
+                (0..=haystack.len()).any(|i| naive(rest, &haystack[i..], case_insensitive))
+            }
+            Some((&expected, rest)) => match haystack.split_first() {
+                Some((&byte, haystack)) => {
+                    let is_match = match case_insensitive {
+                        true => expected.eq_ignore_ascii_case(&byte),
+                        false => expected == byte,
+                    };
+                    (expected == b'_' || is_match) && naive(rest, haystack, case_insensitive)
+                }
+                None => false,
+            },
+        }
+    }
+
+    #[test]
+    fn like_naive() {
+        let haystacks = [
+            &b""[..],
+            b"a",
+            b"ab",
+            b"abc",
+            b"abcabc",
+            b"aBcAbC",
+            b"xyzabcxyz",
+            b"a-b-c-d",
+            b"Lorem ipsum dolor sit amet, consectetur adipiscing elit",
+        ];
+        let patterns = [
+            &b""[..],
+            b"%",
+            b"%%",
+            b"_",
+            b"__",
+            b"a",
+            b"a%",
+            b"%a",
+            b"%a%",
+            b"a%c",
+            b"a_c",
+            b"%b_%",
+            b"%abc%abc%",
+            b"%abc%abc",
+            b"abc%abc",
+            b"%c%b%",
+            b"%_-_%",
+            b"a%-%-%d",
+            b"%A%C",
+            b"%ipsum%sit%",
+            b"%IPSUM _OLOR%",
+            b"%or%or%or%or%",
+            b"%_%_%_%_%",
+            b"%x_z%",
+        ];
+
+        for pattern in patterns {
+            let like = LikeMatcher::like(pattern, None).unwrap();
+            let ilike = LikeMatcher::ilike(pattern, None).unwrap();
+
+            for haystack in haystacks {
+                let message = (
+                    core::str::from_utf8(pattern).unwrap(),
+                    core::str::from_utf8(haystack).unwrap(),
+                );
+                assert_eq!(
+                    like.matches(haystack),
+                    naive(pattern, haystack, false),
+                    "{message:?}"
+                );
+                assert_eq!(
+                    ilike.matches(haystack),
+                    naive(pattern, haystack, true),
+                    "{message:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn like_long_haystack() {
+        let mut haystack = b"ab".repeat(100);
+        haystack.extend_from_slice(b"-x-");
+        haystack.extend(b"AB".repeat(100));
+
+        assert!(LikeMatcher::like(b"%b-_-A%", None)
+            .unwrap()
+            .matches(&haystack));
+        assert!(LikeMatcher::like(b"a%-x-%B", None)
+            .unwrap()
+            .matches(&haystack));
+        assert!(!LikeMatcher::like(b"%aba%-%aba%", None)
+            .unwrap()
+            .matches(&haystack));
+        assert!(LikeMatcher::ilike(b"%aba%-%bab%", None)
+            .unwrap()
+            .matches(&haystack));
+        assert!(!LikeMatcher::ilike(b"%aba%-%bab%x", None)
+            .unwrap()
+            .matches(&haystack));
+    }
+
+    #[test]
+    fn like_escape() {
+        let matcher = LikeMatcher::like(b"50!%!_off%", Some(b'!')).unwrap();
+        assert!(matcher.matches(b"50%_off today"));
+        assert!(!matcher.matches(b"50%-off today"));
+        assert!(!matcher.matches(b"500_off today"));
+
+        let matcher = LikeMatcher::like(b"%a!!b%", Some(b'!')).unwrap();
+        assert!(matcher.matches(b"xa!bx"));
+        assert!(!matcher.matches(b"xa!!bx"));
+
+        // Escaping an ordinary byte makes it match itself.
+        let matcher = LikeMatcher::like(b"\\a\\%", Some(b'\\')).unwrap();
+        assert!(matcher.matches(b"a%"));
+        assert!(!matcher.matches(b"\\a%"));
+
+        // Without an escape character, backslashes are ordinary bytes.
+        let matcher = LikeMatcher::like(b"\\%", None).unwrap();
+        assert!(matcher.matches(b"\\foo"));
+        assert!(!matcher.matches(b"%"));
+
+        // The escape character takes precedence over wildcards.
+        let matcher = LikeMatcher::like(b"%%a", Some(b'%')).unwrap();
+        assert!(matcher.matches(b"%a"));
+        assert!(!matcher.matches(b"ba"));
+    }
+
+    #[test]
+    fn like_invalid_escape() {
+        let error = LikeMatcher::like(b"abc\\", Some(b'\\')).unwrap_err();
+        assert_eq!(error, LikeError { offset: 3 });
+        assert_eq!(error.offset(), 3);
+
+        assert!(LikeMatcher::ilike(b"abc\\\\", Some(b'\\')).is_ok());
+        assert!(LikeMatcher::ilike(b"abc\\\\\\", Some(b'\\')).is_err());
+    }
+}
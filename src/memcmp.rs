@@ -1,4 +1,4 @@
-use std::slice;
+use core::slice;
 
 #[allow(dead_code)]
 #[multiversion::multiversion]
@@ -23,3 +23,35 @@ pub unsafe fn generic(left: *const u8, right: *const u8, n: usize) -> bool {
 pub unsafe fn specialized<const N: usize>(left: *const u8, right: *const u8) -> bool {
     slice::from_raw_parts(left, N) == slice::from_raw_parts(right, N)
 }
+
+/// Compares `n` bytes, using the comparisons specialized for their number when
+/// it is small.
+#[multiversion::multiversion]
+#[clone(target = "[x86|x86_64]+avx2")]
+#[clone(target = "wasm32+simd128")]
+#[cfg_attr(
+    all(target_arch = "aarch64", feature = "aarch64"),
+    clone(target = "aarch64+neon")
+)]
+pub unsafe fn equal(left: *const u8, right: *const u8, n: usize) -> bool {
+    match n {
+        0 => dispatch!(specialized::<0>(left, right)),
+        1 => dispatch!(specialized::<1>(left, right)),
+        2 => dispatch!(specialized::<2>(left, right)),
+        3 => dispatch!(specialized::<3>(left, right)),
+        4 => dispatch!(specialized::<4>(left, right)),
+        5 => dispatch!(specialized::<5>(left, right)),
+        6 => dispatch!(specialized::<6>(left, right)),
+        7 => dispatch!(specialized::<7>(left, right)),
+        8 => dispatch!(specialized::<8>(left, right)),
+        9 => dispatch!(specialized::<9>(left, right)),
+        10 => dispatch!(specialized::<10>(left, right)),
+        11 => dispatch!(specialized::<11>(left, right)),
+        12 => dispatch!(specialized::<12>(left, right)),
+        13 => dispatch!(specialized::<13>(left, right)),
+        14 => dispatch!(specialized::<14>(left, right)),
+        15 => dispatch!(specialized::<15>(left, right)),
+        16 => dispatch!(specialized::<16>(left, right)),
+        _ => dispatch!(generic(left, right, n)),
+    }
+}
@@ -0,0 +1,352 @@
+//! Drop-in replacements for the finders of `memchr::memmem`.
+//!
+//! The types in this module have the same methods and signatures as their
+//! counterparts in `memchr::memmem`, so that switching between both
+//! implementations only requires changing an import. Searches are performed by
+//! `sliceslice::Finder`, with the `position` of the needle chosen as its least
+//! common byte according to a table of byte frequencies.
+//!
+//! # Example
+//!
+//! ```
+//! use sliceslice::memmem::{Finder, FinderRev};
+//!
+//! let haystack = b"foo bar foo baz foo";
+//!
+//! let finder = Finder::new("foo");
+//! assert_eq!(finder.find(haystack), Some(0));
+//! assert_eq!(finder.find_iter(haystack).collect::<Vec<_>>(), [0, 8, 16]);
+//!
+//! let finder = FinderRev::new("foo");
+//! assert_eq!(finder.rfind(haystack), Some(16));
+//! assert_eq!(finder.rfind_iter(haystack).collect::<Vec<_>>(), [16, 8, 0]);
+//! ```
+
+use crate::rank::rarest_position;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+/// Needle which is either borrowed or, with the `alloc` feature, owned.
+#[derive(Clone, Debug)]
+enum CowBytes<'n> {
+    Borrowed(&'n [u8]),
+    #[cfg(feature = "alloc")]
+    Owned(Box<[u8]>),
+}
+
+impl CowBytes<'_> {
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            Self::Borrowed(needle) => needle,
+            #[cfg(feature = "alloc")]
+            Self::Owned(needle) => needle,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn into_owned(self) -> CowBytes<'static> {
+        match self {
+            Self::Borrowed(needle) => CowBytes::Owned(needle.into()),
+            Self::Owned(needle) => CowBytes::Owned(needle),
+        }
+    }
+}
+
+/// Creates the finder used to search for `needle`.
+#[inline]
+fn finder(needle: &[u8], position: usize) -> crate::Finder<&[u8]> {
+    crate::Finder::__from_parts(needle, position)
+}
+
+/// A single substring searcher fixed to a particular needle, with the same
+/// interface as `memchr::memmem::Finder`.
+#[derive(Clone, Debug)]
+pub struct Finder<'n> {
+    needle: CowBytes<'n>,
+    position: usize,
+}
+
+impl<'n> Finder<'n> {
+    /// Creates a new finder for the given needle.
+    #[inline]
+    pub fn new<B: ?Sized + AsRef<[u8]>>(needle: &'n B) -> Finder<'n> {
+        let needle = needle.as_ref();
+
+        Finder {
+            needle: CowBytes::Borrowed(needle),
+            position: rarest_position(needle),
+        }
+    }
+
+    /// Returns the index of the first occurrence of this needle in the given
+    /// haystack.
+    #[inline]
+    pub fn find(&self, haystack: &[u8]) -> Option<usize> {
+        finder(self.needle(), self.position).find_in(haystack)
+    }
+
+    /// Returns an iterator over all non-overlapping occurrences of this needle
+    /// in the given haystack.
+    #[inline]
+    pub fn find_iter<'a, 'h>(&'a self, haystack: &'h [u8]) -> FindIter<'h, 'a> {
+        FindIter {
+            haystack,
+            finder: self.as_ref(),
+            pos: 0,
+        }
+    }
+
+    /// Converts this finder into its owned variant, such that it no longer
+    /// borrows the needle. This copies the needle if it is borrowed.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn into_owned(self) -> Finder<'static> {
+        Finder {
+            needle: self.needle.into_owned(),
+            position: self.position,
+        }
+    }
+
+    /// Converts this finder into its borrowed variant.
+    #[inline]
+    pub fn as_ref(&self) -> Finder<'_> {
+        Finder {
+            needle: CowBytes::Borrowed(self.needle()),
+            position: self.position,
+        }
+    }
+
+    /// Returns the needle that this finder searches for.
+    #[inline]
+    pub fn needle(&self) -> &[u8] {
+        self.needle.as_slice()
+    }
+}
+
+/// A single substring reverse searcher fixed to a particular needle, with the
+/// same interface as `memchr::memmem::FinderRev`.
+#[derive(Clone, Debug)]
+pub struct FinderRev<'n> {
+    needle: CowBytes<'n>,
+    position: usize,
+}
+
+impl<'n> FinderRev<'n> {
+    /// Creates a new reverse finder for the given needle.
+    #[inline]
+    pub fn new<B: ?Sized + AsRef<[u8]>>(needle: &'n B) -> FinderRev<'n> {
+        let needle = needle.as_ref();
+
+        FinderRev {
+            needle: CowBytes::Borrowed(needle),
+            position: rarest_position(needle),
+        }
+    }
+
+    /// Returns the index of the last occurrence of this needle in the given
+    /// haystack.
+    #[inline]
+    pub fn rfind<B: AsRef<[u8]>>(&self, haystack: B) -> Option<usize> {
+        finder(self.needle(), self.position).rfind_in(haystack.as_ref())
+    }
+
+    /// Returns a reverse iterator over all non-overlapping occurrences of this
+    /// needle in the given haystack.
+    #[inline]
+    pub fn rfind_iter<'a, 'h>(&'a self, haystack: &'h [u8]) -> FindRevIter<'h, 'a> {
+        FindRevIter {
+            haystack,
+            finder: self.as_ref(),
+            pos: Some(haystack.len()),
+        }
+    }
+
+    /// Converts this finder into its owned variant, such that it no longer
+    /// borrows the needle. This copies the needle if it is borrowed.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn into_owned(self) -> FinderRev<'static> {
+        FinderRev {
+            needle: self.needle.into_owned(),
+            position: self.position,
+        }
+    }
+
+    /// Converts this finder into its borrowed variant.
+    #[inline]
+    pub fn as_ref(&self) -> FinderRev<'_> {
+        FinderRev {
+            needle: CowBytes::Borrowed(self.needle()),
+            position: self.position,
+        }
+    }
+
+    /// Returns the needle that this finder searches for.
+    #[inline]
+    pub fn needle(&self) -> &[u8] {
+        self.needle.as_slice()
+    }
+}
+
+/// An iterator over non-overlapping substring matches, created by
+/// `Finder::find_iter`.
+///
+/// `'h` is the lifetime of the haystack while `'n` is the lifetime of the
+/// needle.
+#[derive(Clone, Debug)]
+pub struct FindIter<'h, 'n> {
+    haystack: &'h [u8],
+    finder: Finder<'n>,
+    pos: usize,
+}
+
+impl<'h> FindIter<'h, '_> {
+    /// Converts this iterator into its owned variant, such that it no longer
+    /// borrows the finder and needle.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn into_owned(self) -> FindIter<'h, 'static> {
+        FindIter {
+            haystack: self.haystack,
+            finder: self.finder.into_owned(),
+            pos: self.pos,
+        }
+    }
+}
+
+impl Iterator for FindIter<'_, '_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let haystack = self.haystack.get(self.pos..)?;
+        let pos = self.pos + self.finder.find(haystack)?;
+
+        // Empty needles match at every offset, including the very end.
+        self.pos = pos + self.finder.needle().len().max(1);
+
+        Some(pos)
+    }
+}
+
+/// An iterator over non-overlapping substring matches in reverse, created by
+/// `FinderRev::rfind_iter`.
+///
+/// `'h` is the lifetime of the haystack while `'n` is the lifetime of the
+/// needle.
+#[derive(Clone, Debug)]
+pub struct FindRevIter<'h, 'n> {
+    haystack: &'h [u8],
+    finder: FinderRev<'n>,
+    // Set to `None` after an empty needle has matched at offset zero.
+    pos: Option<usize>,
+}
+
+impl<'h> FindRevIter<'h, '_> {
+    /// Converts this iterator into its owned variant, such that it no longer
+    /// borrows the finder and needle.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn into_owned(self) -> FindRevIter<'h, 'static> {
+        FindRevIter {
+            haystack: self.haystack,
+            finder: self.finder.into_owned(),
+            pos: self.pos,
+        }
+    }
+}
+
+impl Iterator for FindRevIter<'_, '_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let pos = self.pos?;
+        let index = self.finder.rfind(&self.haystack[..pos])?;
+
+        self.pos = match index == pos {
+            true => pos.checked_sub(1),
+            false => Some(index),
+        };
+
+        Some(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Finder, FinderRev};
+
+    const HAYSTACKS: [&[u8]; 5] = [
+        b"",
+        b"a",
+        b"foo bar foo baz foo",
+        b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+        b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod",
+    ];
+
+    const NEEDLES: [&[u8]; 8] = [
+        b"",
+        b"a",
+        b"aa",
+        b"aaa",
+        b"foo",
+        b"ipsum",
+        b"elit",
+        b"dolor sit amet",
+    ];
+
+    #[test]
+    fn memmem_finder() {
+        for needle in NEEDLES {
+            let finder = Finder::new(needle);
+            let expected = memchr::memmem::Finder::new(needle);
+
+            for haystack in HAYSTACKS {
+                assert_eq!(finder.find(haystack), expected.find(haystack));
+                assert!(finder.find_iter(haystack).eq(expected.find_iter(haystack)));
+            }
+
+            assert_eq!(finder.needle(), expected.needle());
+        }
+    }
+
+    #[test]
+    fn memmem_finder_rev() {
+        for needle in NEEDLES {
+            let finder = FinderRev::new(needle);
+            let expected = memchr::memmem::FinderRev::new(needle);
+
+            for haystack in HAYSTACKS {
+                assert_eq!(finder.rfind(haystack), expected.rfind(haystack));
+                assert!(finder
+                    .rfind_iter(haystack)
+                    .eq(expected.rfind_iter(haystack)));
+            }
+
+            assert_eq!(finder.needle(), expected.needle());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn memmem_into_owned() {
+        let finder = {
+            let needle = b"foo".to_vec();
+            Finder::new(&needle).into_owned()
+        };
+        assert_eq!(finder.as_ref().find(b"foo bar foo"), Some(0));
+
+        let finder = {
+            let needle = b"foo".to_vec();
+            FinderRev::new(&needle).into_owned()
+        };
+        assert_eq!(finder.as_ref().rfind(b"foo bar foo"), Some(8));
+
+        let finder = Finder::new("foo");
+        let mut iter = finder.find_iter(b"foo bar foo").into_owned();
+        drop(finder);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), Some(8));
+        assert_eq!(iter.next(), None);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{rank::needle_rank, Finder, Needle, NeedleWithSize};
+
+impl<N: Needle> Finder<N> {
+    /// Returns an iterator over the places where the `needle` and the needle
+    /// of `other` both occur in `haystack` with at most `max_distance` bytes
+    /// between them, in either order. Occurrences which touch or overlap are
+    /// at a distance of zero.
+    ///
+    /// The iterator yields the offset of each occurrence of the `needle` which
+    /// has an occurrence of the needle of `other` close enough to it, paired
+    /// with the offset of the leftmost such occurrence. The scan is driven by
+    /// whichever needle is estimated to be the rarer one from its bytes, see the
+    /// `rank` module, and the other needle is only looked for in a bounded
+    /// window around each occurrence of the driving one. Which needle drives
+    /// the scan does not change the results.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sliceslice::Finder;
+    ///
+    /// let haystack = b"aws_secret = 'abc', region = 'us-east-1', user = 'aws_secret'";
+    ///
+    /// let key = Finder::new(&b"aws_secret"[..]);
+    /// let quote = Finder::new(&b"'"[..]);
+    ///
+    /// let near = key.find_near(&quote, 3, haystack).collect::<Vec<_>>();
+    /// assert_eq!(near, [(0, 13), (50, 49)]);
+    ///
+    /// let near = quote.find_near(&key, 3, haystack).collect::<Vec<_>>();
+    /// assert_eq!(near, [(13, 0), (49, 50), (60, 50)]);
+    /// ```
+    pub fn find_near<'f, 'h, M: Needle>(
+        &'f self,
+        other: &'f Finder<M>,
+        max_distance: usize,
+        haystack: &'h [u8],
+    ) -> FindNear<'f, 'h, N, M> {
+        let rarity = |needle: &[u8]| (needle_rank(needle), usize::MAX - needle.len());
+
+        FindNear {
+            finder: self,
+            other,
+            max_distance,
+            haystack,
+            position: 0,
+            other_position: 0,
+            other_occurrence: None,
+            is_driving: rarity(self.needle().as_bytes()) <= rarity(other.needle().as_bytes()),
+        }
+    }
+}
+
+/// Iterator over the places where two needles occur close to each other,
+/// created by `Finder::find_near`.
+#[derive(Clone, Debug)]
+pub struct FindNear<'f, 'h, N: Needle, M: Needle> {
+    finder: &'f Finder<N>,
+    other: &'f Finder<M>,
+    max_distance: usize,
+    haystack: &'h [u8],
+    // Offset from which the next occurrence of the needle of `finder` is
+    // searched.
+    position: usize,
+    // Offset from which the next occurrence of the needle of `other` is
+    // searched, when the scan is driven by `other`.
+    other_position: usize,
+    // Occurrence of the needle of `other` around which occurrences of the
+    // needle of `finder` are being enumerated, when the scan is driven by
+    // `other`.
+    other_occurrence: Option<usize>,
+    // Whether the scan is driven by `finder` rather than by `other`.
+    is_driving: bool,
+}
+
+impl<N: Needle, M: Needle> Iterator for FindNear<'_, '_, N, M> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.is_driving {
+            loop {
+                let rest = self.haystack.get(self.position..)?;
+                let offset = self.position + self.finder.find_in(rest)?;
+                self.position = offset + 1;
+
+                if let Some(found) = self.window(self.other, self.finder, offset, 0) {
+                    return Some((offset, found));
+                }
+            }
+        }
+
+        // Each occurrence of the needle of `finder` is paired with the first
+        // occurrence of the needle of `other` whose window contains it, which
+        // is the leftmost one close enough to it.
+        loop {
+            if let Some(other_offset) = self.other_occurrence {
+                let window = self.window(self.finder, self.other, other_offset, self.position);
+                if let Some(found) = window {
+                    self.position = found + 1;
+                    return Some((found, other_offset));
+                }
+            }
+
+            let rest = self.haystack.get(self.other_position..)?;
+            let other_offset = self.other_position + self.other.find_in(rest)?;
+            self.other_position = other_offset + 1;
+            self.other_occurrence = Some(other_offset);
+        }
+    }
+}
+
+impl<N: Needle, M: Needle> FindNear<'_, '_, N, M> {
+    /// Returns the offset of the leftmost occurrence of the `probed` needle,
+    /// starting at or after `from`, within `max_distance` bytes of the
+    /// occurrence of the `driving` needle at `offset`.
+    fn window<P: Needle, D: Needle>(
+        &self,
+        probed: &Finder<P>,
+        driving: &Finder<D>,
+        offset: usize,
+        from: usize,
+    ) -> Option<usize> {
+        let probed_size = probed.needle().size();
+        let driving_size = driving.needle().size();
+
+        // Occurrences of the probed needle must end at most `max_distance`
+        // bytes before the driving one starts, and start at most `max_distance`
+        // bytes after it ends.
+        let start = offset
+            .saturating_sub(self.max_distance.saturating_add(probed_size))
+            .max(from);
+        let end = (offset + driving_size)
+            .saturating_add(self.max_distance)
+            .saturating_add(probed_size)
+            .min(self.haystack.len());
+
+        Some(start + probed.find_in(self.haystack.get(start..end)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Finder;
+
+    #[test]
+    fn find_near_naive() {
+        let haystack =
+            b"aws_secret: AKIA0123456789ABCDEFGH, x aws_secret ..... AKIA; AKIAaws_secret";
+
+        for (a, b) in [
+            (&b"aws_secret"[..], &b"AKIA"[..]),
+            (b"AKIA", b"aws_secret"),
+            (b"a", b"e"),
+            (b"aws", b"secret"),
+            (b"", b"AKIA"),
+            (b"AKIA", b""),
+            (b"x", b"y"),
+        ] {
+            let occurrences = |needle: &[u8]| {
+                (0..=haystack.len() - needle.len())
+                    .filter(|&i| haystack[i..].starts_with(needle))
+                    .collect::<Vec<_>>()
+            };
+
+            let finder_a = Finder::new(a);
+            let finder_b = Finder::new(b);
+
+            for max_distance in [0, 1, 2, 5, 10, 100] {
+                let is_near = |i: usize, j: usize| {
+                    j + b.len() + max_distance >= i && j <= i + a.len() + max_distance
+                };
+
+                let expected = occurrences(a)
+                    .into_iter()
+                    .filter_map(|i| Some((i, occurrences(b).into_iter().find(|&j| is_near(i, j))?)))
+                    .collect::<Vec<_>>();
+
+                let iter = finder_a.find_near(&finder_b, max_distance, haystack);
+                assert_eq!(
+                    iter.collect::<Vec<_>>(),
+                    expected,
+                    "{a:?} {b:?} {max_distance}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn find_near_rarer_needle() {
+        let common = Finder::new(&b"e"[..]);
+        let rare = Finder::new(&b"zq"[..]);
+
+        assert!(!common.find_near(&rare, 0, b"").is_driving);
+        assert!(rare.find_near(&common, 0, b"").is_driving);
+
+        // Needles of the same rank are driven by the longest one.
+        let short = Finder::new(&b"ee"[..]);
+        let long = Finder::new(&b"eee"[..]);
+        assert!(!short.find_near(&long, 0, b"").is_driving);
+        assert!(long.find_near(&short, 0, b"").is_driving);
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{Finder, Needle, NeedleWithSize};
+use rayon::prelude::*;
+
+/// Size of the chunks of the haystack searched by each task, excluding the
+/// overlap with the next chunk.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// Splits `haystack` into chunks of `chunk_size` bytes, each extended by
+/// `size - 1` bytes so that every occurrence of a needle of length `size` lies
+/// entirely within the chunk in which it starts. Yields the offset of each
+/// chunk along with the chunk itself.
+fn chunks(
+    haystack: &[u8],
+    size: usize,
+    chunk_size: usize,
+) -> impl IndexedParallelIterator<Item = (usize, &[u8])> {
+    let count = haystack.len().div_ceil(chunk_size);
+    (0..count)
+        .into_par_iter()
+        .map(move |i| chunk(haystack, size, chunk_size, i))
+}
+
+/// Returns the offset and contents of the `i`-th chunk yielded by `chunks`.
+fn chunk(haystack: &[u8], size: usize, chunk_size: usize, i: usize) -> (usize, &[u8]) {
+    let start = i * chunk_size;
+    let end = (start + chunk_size + size.saturating_sub(1)).min(haystack.len());
+    (start, &haystack[start..end])
+}
+
+impl<N: Needle + Sync> Finder<N> {
+    /// Same as `find_in` but searches chunks of `haystack` in parallel using
+    /// `rayon`. The offset of the leftmost occurrence is still returned.
+    pub fn par_find(&self, haystack: &[u8]) -> Option<usize> {
+        self.par_find_chunked(haystack, CHUNK_SIZE)
+    }
+
+    /// Same as `search_in` but searches chunks of `haystack` in parallel using
+    /// `rayon`, stopping as soon as any occurrence is found.
+    pub fn par_contains(&self, haystack: &[u8]) -> bool {
+        self.par_contains_chunked(haystack, CHUNK_SIZE)
+    }
+
+    /// Returns the number of non-overlapping occurrences of the `needle` within
+    /// `haystack`, as found from left to right, counting chunks of `haystack`
+    /// in parallel using `rayon`.
+    pub fn par_count(&self, haystack: &[u8]) -> usize {
+        self.par_count_chunked(haystack, CHUNK_SIZE)
+    }
+
+    fn par_find_chunked(&self, haystack: &[u8], chunk_size: usize) -> Option<usize> {
+        let size = self.needle().size();
+        if size == 0 {
+            return Some(0);
+        }
+
+        chunks(haystack, size, chunk_size)
+            .find_map_first(|(start, chunk)| Some(start + self.find_in(chunk)?))
+    }
+
+    fn par_contains_chunked(&self, haystack: &[u8], chunk_size: usize) -> bool {
+        let size = self.needle().size();
+        if size == 0 {
+            return true;
+        }
+
+        chunks(haystack, size, chunk_size).any(|(_, chunk)| self.search_in(chunk))
+    }
+
+    fn par_count_chunked(&self, haystack: &[u8], chunk_size: usize) -> usize {
+        let size = self.needle().size();
+        if size == 0 {
+            return haystack.len() + 1;
+        }
+
+        let counts = chunks(haystack, size, chunk_size)
+            .map(|(start, chunk)| {
+                let limit = chunk_size.min(chunk.len());
+                let (count, next) = self.count_below(chunk, limit);
+                (count, start + next)
+            })
+            .collect::<Vec<_>>();
+
+        // The last occurrence counted before a chunk can overlap with its
+        // first `size - 1` bytes, in which case the count resumes after it,
+        // until reaching an occurrence also counted from the start of the
+        // chunk. Both counts agree from there on. On periodic inputs they may
+        // never meet, in which case the resumed count covers the whole chunk.
+        let mut count = 0;
+        let mut next = 0;
+        for (i, (chunk_count, chunk_next)) in counts.into_iter().enumerate() {
+            let (start, chunk) = chunk(haystack, size, chunk_size, i);
+            if next <= start {
+                count += chunk_count;
+                next = chunk_next;
+                continue;
+            }
+
+            let limit = chunk_size.min(chunk.len());
+            let mut resumed = next - start;
+            // First occurrence counted from the start of the chunk which is
+            // not before the last one found by the resumed count, along with
+            // the number of those before it.
+            let mut visited = self.find_below(chunk, 0, limit);
+            let mut skipped = 0;
+            next = loop {
+                let Some(found) = self.find_below(chunk, resumed, limit) else {
+                    break start + resumed;
+                };
+
+                while let Some(offset) = visited.filter(|&offset| offset < found) {
+                    visited = self.find_below(chunk, offset + size, limit);
+                    skipped += 1;
+                }
+
+                if visited == Some(found) {
+                    count += chunk_count - skipped;
+                    break chunk_next;
+                }
+
+                count += 1;
+                resumed = found + size;
+            };
+        }
+
+        count
+    }
+
+    /// Counts the non-overlapping occurrences of the `needle` starting in the
+    /// first `limit` bytes of `chunk`, as found from its start, and returns
+    /// their number along with the offset following the last of them.
+    fn count_below(&self, chunk: &[u8], limit: usize) -> (usize, usize) {
+        let mut count = 0;
+        let mut next = 0;
+        while let Some(found) = self.find_below(chunk, next, limit) {
+            count += 1;
+            next = found + self.needle().size();
+        }
+
+        (count, next)
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` in `chunk`
+    /// starting at or after `from` and before `limit`, if any.
+    #[inline]
+    fn find_below(&self, chunk: &[u8], from: usize, limit: usize) -> Option<usize> {
+        self.find_at(chunk, from).filter(|&found| found < limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Finder;
+
+    fn haystack() -> Vec<u8> {
+        let mut haystack = Vec::new();
+        for i in 0..500 {
+            haystack.extend_from_slice(b"abc");
+            haystack.extend(std::iter::repeat_n(b'a', i % 7));
+        }
+        haystack
+    }
+
+    #[test]
+    fn par_find() {
+        let haystack = haystack();
+
+        for needle in [
+            &b""[..],
+            b"a",
+            b"aa",
+            b"aaa",
+            b"abca",
+            b"caaaaaa",
+            b"abcabcabc",
+        ] {
+            let finder = Finder::new(needle);
+            let expected = finder.find_in(&haystack);
+
+            assert_eq!(finder.par_find(&haystack), expected);
+            assert_eq!(finder.par_contains(&haystack), expected.is_some());
+
+            for chunk_size in [1, 2, 3, 7, 64, 1000] {
+                assert_eq!(finder.par_find_chunked(&haystack, chunk_size), expected);
+                assert_eq!(
+                    finder.par_contains_chunked(&haystack, chunk_size),
+                    expected.is_some()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn par_count() {
+        let haystack = haystack();
+
+        for needle in [
+            &b""[..],
+            b"a",
+            b"aa",
+            b"aaa",
+            b"abca",
+            b"caaaaaa",
+            b"abcabcabc",
+        ] {
+            let finder = Finder::new(needle);
+            let expected = memchr::memmem::find_iter(&haystack, needle).count();
+
+            assert_eq!(finder.par_count(&haystack), expected);
+
+            for chunk_size in [1, 2, 3, 7, 64, 1000] {
+                assert_eq!(finder.par_count_chunked(&haystack, chunk_size), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn par_count_periodic() {
+        let mut haystack = vec![b'a'; 1000];
+        haystack.extend(b"ab".repeat(500));
+        haystack.extend(b"aab".repeat(300));
+
+        for needle in [
+            &b"aa"[..],
+            b"aaa",
+            b"aaaaaaaaaaaaa",
+            b"abab",
+            b"ababababa",
+            b"aabaab",
+            b"baa",
+        ] {
+            let finder = Finder::new(needle);
+            let expected = memchr::memmem::find_iter(&haystack, needle).count();
+
+            for chunk_size in [1, 2, 3, 4, 5, 7, 12, 64] {
+                assert_eq!(
+                    finder.par_count_chunked(&haystack, chunk_size),
+                    expected,
+                    "{needle:?} {chunk_size}"
+                );
+            }
+        }
+    }
+}
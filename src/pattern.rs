@@ -0,0 +1,364 @@
+use crate::{Finder, Needle};
+use core::str::pattern::{Pattern, ReverseSearcher, SearchStep, Searcher};
+
+/// Searcher state machine for a `Finder` used as a string `Pattern`.
+///
+/// Matches are reported from the front and from the back of the part of the
+/// haystack which has not been consumed yet, which is delimited by `position`
+/// and `end`. Needles which are not valid UTF-8 never match, as their matches
+/// could otherwise split a character of the haystack.
+#[derive(Clone, Debug)]
+pub struct FinderSearcher<'a, 'b, N: Needle> {
+    haystack: &'a str,
+    finder: &'b Finder<N>,
+    is_utf8: bool,
+    position: usize,
+    end: usize,
+    // State used only by the empty needle, which matches at every character
+    // boundary, mirroring the standard library's `StrSearcher`.
+    is_match_fw: bool,
+    is_match_bw: bool,
+    is_finished: bool,
+}
+
+impl<'a, 'b, N: Needle> FinderSearcher<'a, 'b, N> {
+    fn new(haystack: &'a str, finder: &'b Finder<N>) -> Self {
+        Self {
+            haystack,
+            finder,
+            is_utf8: core::str::from_utf8(finder.needle().as_bytes()).is_ok(),
+            position: 0,
+            end: haystack.len(),
+            is_match_fw: true,
+            is_match_bw: true,
+            is_finished: false,
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.finder.needle().as_bytes().len()
+    }
+
+    #[inline]
+    fn remaining(&self) -> &'a [u8] {
+        &self.haystack.as_bytes()[self.position..self.end]
+    }
+
+    fn next_empty(&mut self) -> SearchStep {
+        if self.is_finished {
+            return SearchStep::Done;
+        }
+
+        let is_match = self.is_match_fw;
+        self.is_match_fw = !self.is_match_fw;
+        let position = self.position;
+
+        match self.haystack[position..self.end].chars().next() {
+            _ if is_match => SearchStep::Match(position, position),
+            None => {
+                self.is_finished = true;
+                SearchStep::Done
+            }
+            Some(ch) => {
+                self.position += ch.len_utf8();
+                SearchStep::Reject(position, self.position)
+            }
+        }
+    }
+
+    fn next_back_empty(&mut self) -> SearchStep {
+        if self.is_finished {
+            return SearchStep::Done;
+        }
+
+        let is_match = self.is_match_bw;
+        self.is_match_bw = !self.is_match_bw;
+        let end = self.end;
+
+        match self.haystack[self.position..end].chars().next_back() {
+            _ if is_match => SearchStep::Match(end, end),
+            None => {
+                self.is_finished = true;
+                SearchStep::Done
+            }
+            Some(ch) => {
+                self.end -= ch.len_utf8();
+                SearchStep::Reject(self.end, end)
+            }
+        }
+    }
+}
+
+unsafe impl<'a, N: Needle> Searcher<'a> for FinderSearcher<'a, '_, N> {
+    #[inline]
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let size = self.size();
+        if size == 0 {
+            return self.next_empty();
+        }
+
+        if self.position >= self.end {
+            return SearchStep::Done;
+        }
+
+        let start = self.position;
+        let found = match self.is_utf8 {
+            true => self.finder.find_in(self.remaining()),
+            false => None,
+        };
+
+        match found {
+            Some(0) => {
+                self.position += size;
+                SearchStep::Match(start, self.position)
+            }
+            // Report the rejected bytes first, the match is found again by the
+            // next call, immediately at the start of the remaining haystack.
+            Some(offset) => {
+                self.position += offset;
+                SearchStep::Reject(start, self.position)
+            }
+            None => {
+                self.position = self.end;
+                SearchStep::Reject(start, self.end)
+            }
+        }
+    }
+
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        let size = self.size();
+        if size == 0 {
+            loop {
+                match self.next_empty() {
+                    SearchStep::Match(start, end) => return Some((start, end)),
+                    SearchStep::Reject(..) => continue,
+                    SearchStep::Done => return None,
+                }
+            }
+        }
+
+        if !self.is_utf8 || self.position >= self.end {
+            self.position = self.end;
+            return None;
+        }
+
+        match self.finder.find_in(self.remaining()) {
+            Some(offset) => {
+                let start = self.position + offset;
+                self.position = start + size;
+                Some((start, self.position))
+            }
+            None => {
+                self.position = self.end;
+                None
+            }
+        }
+    }
+}
+
+unsafe impl<'a, N: Needle> ReverseSearcher<'a> for FinderSearcher<'a, '_, N> {
+    fn next_back(&mut self) -> SearchStep {
+        let size = self.size();
+        if size == 0 {
+            return self.next_back_empty();
+        }
+
+        if self.position >= self.end {
+            return SearchStep::Done;
+        }
+
+        let end = self.end;
+        let found = match self.is_utf8 {
+            true => self.finder.rfind_in(self.remaining()),
+            false => None,
+        };
+
+        match found {
+            Some(offset) if self.position + offset + size == end => {
+                self.end -= size;
+                SearchStep::Match(self.end, end)
+            }
+            Some(offset) => {
+                self.end = self.position + offset + size;
+                SearchStep::Reject(self.end, end)
+            }
+            None => {
+                self.end = self.position;
+                SearchStep::Reject(self.position, end)
+            }
+        }
+    }
+
+    fn next_match_back(&mut self) -> Option<(usize, usize)> {
+        let size = self.size();
+        if size == 0 {
+            loop {
+                match self.next_back_empty() {
+                    SearchStep::Match(start, end) => return Some((start, end)),
+                    SearchStep::Reject(..) => continue,
+                    SearchStep::Done => return None,
+                }
+            }
+        }
+
+        if !self.is_utf8 || self.position >= self.end {
+            self.end = self.position;
+            return None;
+        }
+
+        match self.finder.rfind_in(self.remaining()) {
+            Some(offset) => {
+                self.end = self.position + offset;
+                Some((self.end, self.end + size))
+            }
+            None => {
+                self.end = self.position;
+                None
+            }
+        }
+    }
+}
+
+/// Allows using a `Finder` with the string methods of the standard library,
+/// such as `str::contains`, `str::split`, `str::matches` or `str::replace`.
+///
+/// # Example
+///
+/// ```
+/// #![feature(pattern)]
+///
+/// use sliceslice::Finder;
+///
+/// let finder = Finder::new(&b", "[..]);
+///
+/// assert!("foo, bar".contains(&finder));
+/// assert_eq!("foo, bar, baz".split(&finder).collect::<Vec<_>>(), ["foo", "bar", "baz"]);
+/// assert_eq!("foo, bar, baz".replace(&finder, "; "), "foo; bar; baz");
+/// ```
+impl<'b, N: Needle> Pattern for &'b Finder<N> {
+    type Searcher<'a> = FinderSearcher<'a, 'b, N>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &str) -> FinderSearcher<'_, 'b, N> {
+        FinderSearcher::new(haystack, self)
+    }
+
+    #[inline]
+    fn is_contained_in(self, haystack: &str) -> bool {
+        core::str::from_utf8(self.needle().as_bytes()).is_ok()
+            && self.search_in(haystack.as_bytes())
+    }
+
+    #[inline]
+    fn is_prefix_of(self, haystack: &str) -> bool {
+        let needle = self.needle().as_bytes();
+        core::str::from_utf8(needle).is_ok() && haystack.as_bytes().starts_with(needle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Finder;
+
+    const HAYSTACK: &str = "foo, bär, baz, , qux";
+
+    #[test]
+    fn pattern_contains() {
+        assert!(HAYSTACK.contains(&Finder::new(&b"b\xc3\xa4r"[..])));
+        assert!(!HAYSTACK.contains(&Finder::new(&b"quux"[..])));
+        assert!(HAYSTACK.starts_with(&Finder::new(&b"foo"[..])));
+        assert!(!HAYSTACK.starts_with(&Finder::new(&b"bar"[..])));
+    }
+
+    #[test]
+    fn pattern_invalid_utf8() {
+        let finder = Finder::new(&b"\xc3"[..]);
+
+        assert!(!"bär".contains(&finder));
+        assert!(!"bär".starts_with(&finder));
+        assert_eq!("bär".find(&finder), None);
+        assert_eq!("bär".rfind(&finder), None);
+        assert_eq!("bär".split(&finder).collect::<Vec<_>>(), ["bär"]);
+    }
+
+    #[test]
+    fn pattern_split() {
+        let finder = Finder::new(&b", "[..]);
+
+        let expected = ["foo", "bär", "baz", "", "qux"];
+        assert_eq!(HAYSTACK.split(&finder).collect::<Vec<_>>(), expected);
+
+        let mut reversed = expected;
+        reversed.reverse();
+        assert_eq!(HAYSTACK.rsplit(&finder).collect::<Vec<_>>(), reversed);
+
+        assert_eq!(HAYSTACK.split_once(&finder), HAYSTACK.split_once(", "));
+        assert_eq!(HAYSTACK.rsplit_once(&finder), HAYSTACK.rsplit_once(", "));
+    }
+
+    #[test]
+    fn pattern_matches() {
+        let haystack = "aaaaa aaaaaaaaaa";
+
+        for needle in ["a", "aa", "aaa", "aaaa", "a a", " "] {
+            let finder = Finder::new(needle.as_bytes());
+
+            assert_eq!(
+                haystack.matches(&finder).collect::<Vec<_>>(),
+                haystack.matches(needle).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                haystack.match_indices(&finder).collect::<Vec<_>>(),
+                haystack.match_indices(needle).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                haystack.rmatch_indices(&finder).collect::<Vec<_>>(),
+                haystack.rmatch_indices(needle).collect::<Vec<_>>()
+            );
+            assert_eq!(haystack.find(&finder), haystack.find(needle));
+            assert_eq!(haystack.rfind(&finder), haystack.rfind(needle));
+        }
+    }
+
+    #[test]
+    fn pattern_replace() {
+        let finder = Finder::new(&b", "[..]);
+
+        assert_eq!(HAYSTACK.replace(&finder, "; "), "foo; bär; baz; ; qux");
+        assert_eq!(HAYSTACK.replacen(&finder, "; ", 2), "foo; bär; baz, , qux");
+        assert_eq!(
+            HAYSTACK.trim_start_matches(&Finder::new(&b"fo"[..])),
+            HAYSTACK.trim_start_matches("fo")
+        );
+        assert_eq!(
+            HAYSTACK.trim_end_matches(&Finder::new(&b"ux"[..])),
+            HAYSTACK.trim_end_matches("ux")
+        );
+    }
+
+    #[test]
+    fn pattern_empty() {
+        let finder = Finder::new(&b""[..]);
+
+        for haystack in ["", "a", "bär"] {
+            assert_eq!(
+                haystack.split(&finder).collect::<Vec<_>>(),
+                haystack.split("").collect::<Vec<_>>()
+            );
+            assert_eq!(
+                haystack.rsplit(&finder).collect::<Vec<_>>(),
+                haystack.rsplit("").collect::<Vec<_>>()
+            );
+            assert_eq!(
+                haystack.match_indices(&finder).collect::<Vec<_>>(),
+                haystack.match_indices("").collect::<Vec<_>>()
+            );
+            assert_eq!(haystack.replace(&finder, "-"), haystack.replace("", "-"));
+        }
+    }
+}
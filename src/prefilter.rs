@@ -0,0 +1,223 @@
+use crate::{CompiledNeedle, Finder, Needle};
+
+/// Minimum number of candidates to report before `PrefilterState` judges the
+/// effectiveness of a prefilter.
+const MIN_CANDIDATES: u64 = 50;
+
+/// Minimum average number of bytes skipped per candidate for a prefilter to be
+/// considered effective.
+const MIN_AVERAGE_SKIP: u64 = 8;
+
+/// SIMD candidate filter, reporting the positions where a needle could start
+/// without verifying them.
+///
+/// A position is a candidate when the haystack matches the first byte of the
+/// needle and the byte at `position`, which is the same test as the one
+/// performed by the searchers before comparing the whole needle. No occurrence
+/// of the needle is ever skipped, but candidates may turn out not to match, so
+/// a `Prefilter` is meant to be placed in front of another matching engine,
+/// for instance to look for a literal prefix of a regular expression.
+///
+/// Like `Finder`, a `Prefilter` picks the best available implementation when
+/// searching. Effectiveness is tracked by a separate `PrefilterState`, so that
+/// the same prefilter can be shared between searches.
+///
+/// # Example
+///
+/// ```
+/// use sliceslice::{Prefilter, PrefilterState};
+///
+/// let prefilter = Prefilter::new(&b"ipsum"[..]);
+/// let mut state = PrefilterState::new();
+///
+/// let haystack = b"Lorem ipsum dolor sit amet";
+/// let candidate = prefilter.find(haystack, 0, &mut state).unwrap();
+///
+/// assert_eq!(haystack[candidate], b'i');
+/// assert!(state.is_effective());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Prefilter<N: Needle> {
+    finder: Finder<N>,
+}
+
+impl<N: Needle> Prefilter<N> {
+    /// Creates a new prefilter for `needle`. By default, `position` is set to
+    /// the last character in the needle.
+    pub fn new(needle: N) -> Self {
+        Self {
+            finder: Finder::new(needle),
+        }
+    }
+
+    /// Same as `new` but allows additionally specifying the `position` to use.
+    ///
+    /// # Panics
+    ///
+    /// When `needle` is not empty, panics if `position` is not a valid index
+    /// for `needle`, or if the associated `SIZE` constant does not correspond to
+    /// the actual size of `needle`.
+    pub fn with_position(needle: N, position: usize) -> Self {
+        Self {
+            finder: Finder::with_position(needle, position),
+        }
+    }
+
+    /// Creates a new prefilter from a `CompiledNeedle`, using its needle and
+    /// `position`.
+    pub fn from_compiled(compiled: CompiledNeedle<N>) -> Self {
+        Self {
+            finder: Finder::from_compiled(compiled),
+        }
+    }
+
+    /// Returns the needle filtered for by this prefilter.
+    pub fn needle(&self) -> &N {
+        self.finder.needle()
+    }
+
+    /// Returns the offset in `haystack` of the first candidate found at or
+    /// after `start`, recording the number of bytes skipped in `state`.
+    /// Returns `None` if there is no candidate, including when `start` is out
+    /// of bounds.
+    pub fn find(&self, haystack: &[u8], start: usize, state: &mut PrefilterState) -> Option<usize> {
+        let rest = haystack.get(start..)?;
+
+        match self.finder.find_candidate_in(rest) {
+            Some(offset) => {
+                state.candidates += 1;
+                state.skipped += offset as u64;
+                Some(start + offset)
+            }
+            None => {
+                state.skipped += rest.len() as u64;
+                None
+            }
+        }
+    }
+}
+
+/// Statistics about the candidates reported by a `Prefilter`, used to decide
+/// whether it is worth using.
+///
+/// A prefilter reporting candidates every few bytes costs more than it saves,
+/// which happens when the bytes of the needle used by the SIMD hash are common
+/// in the haystacks searched. Callers can check `is_effective` from time to
+/// time and stop using the prefilter once it returns `false`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrefilterState {
+    candidates: u64,
+    skipped: u64,
+}
+
+impl PrefilterState {
+    /// Creates a new state, with no candidates reported yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of candidates reported so far.
+    pub fn candidates(&self) -> u64 {
+        self.candidates
+    }
+
+    /// Returns the number of bytes skipped so far, i.e. which were not part
+    /// of a candidate.
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Returns the average number of bytes skipped before each candidate, or
+    /// `None` if no candidates were reported yet.
+    pub fn average_skip(&self) -> Option<f64> {
+        match self.candidates {
+            0 => None,
+            candidates => Some(self.skipped as f64 / candidates as f64),
+        }
+    }
+
+    /// Returns `false` once enough candidates were reported to tell that the
+    /// prefilter skips too few bytes between them to be worth using.
+    pub fn is_effective(&self) -> bool {
+        self.candidates < MIN_CANDIDATES || self.skipped >= MIN_AVERAGE_SKIP * self.candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Prefilter, PrefilterState};
+
+    #[test]
+    fn prefilter_candidates() {
+        let haystack = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod";
+
+        for needle in [&b"it"[..], b"sit", b"elit", b"tempor", b"adipiscing elit"] {
+            for position in 1..needle.len() {
+                let prefilter = Prefilter::with_position(needle, position);
+                let mut state = PrefilterState::new();
+
+                for start in 0..=haystack.len() {
+                    let candidate = prefilter.find(haystack, start, &mut state);
+                    let expected = (start..(haystack.len() + 1).saturating_sub(needle.len())).find(
+                        |&offset| {
+                            haystack[offset] == needle[0]
+                                && haystack[offset + position] == needle[position]
+                        },
+                    );
+
+                    assert_eq!(candidate, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn prefilter_matches() {
+        let haystack = b"foo bar baz";
+
+        let prefilter = Prefilter::with_position(&b"baz"[..], 1);
+        let mut state = PrefilterState::new();
+
+        assert_eq!(prefilter.find(haystack, 0, &mut state), Some(4));
+        assert_eq!(prefilter.find(haystack, 5, &mut state), Some(8));
+        assert_eq!(prefilter.find(haystack, 9, &mut state), None);
+        assert_eq!(prefilter.find(haystack, 12, &mut state), None);
+
+        assert_eq!(state.candidates(), 2);
+        assert_eq!(state.skipped(), 4 + 3 + 2);
+        assert_eq!(state.average_skip(), Some(4.5));
+
+        // Haystacks no longer than the needle are not compared directly.
+        let mut state = PrefilterState::new();
+        assert_eq!(prefilter.find(b"bar", 0, &mut state), Some(0));
+        assert_eq!(prefilter.find(b"xbar", 1, &mut state), Some(1));
+        assert_eq!(prefilter.find(b"ba", 0, &mut state), None);
+    }
+
+    #[test]
+    fn prefilter_ineffective() {
+        let haystack = [b'a'; 1000];
+
+        let prefilter = Prefilter::new(&b"aaaaab"[..]);
+        let mut state = PrefilterState::new();
+        assert_eq!(state.average_skip(), None);
+
+        let mut start = 0;
+        while let Some(candidate) = prefilter.find(&haystack, start, &mut state) {
+            start = candidate + 1;
+        }
+
+        assert!(state.is_effective());
+
+        let prefilter = Prefilter::with_position(&b"aaaaab"[..], 1);
+        let mut state = PrefilterState::new();
+
+        let mut start = 0;
+        while let Some(candidate) = prefilter.find(&haystack, start, &mut state) {
+            start = candidate + 1;
+        }
+
+        assert!(state.candidates() > 900);
+        assert!(!state.is_effective());
+    }
+}
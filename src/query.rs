@@ -0,0 +1,449 @@
+use crate::{rank::needle_rank, rank::rarest_position, Finder};
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, ops};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// Boolean combination of literal needles, such as
+/// `("error" OR "fatal") AND NOT "healthcheck"`.
+///
+/// Each literal is searched for with its own `Finder`. Evaluation
+/// short-circuits, and the operands of each `AND` and `OR` are reordered when
+/// the query is built so that those most likely to decide the result are
+/// evaluated first: the rarest needles for `AND`, which are likely to be
+/// missing, and the most common ones for `OR`, which are likely to be present.
+/// How common a needle is gets estimated from its bytes alone, see the `rank`
+/// module.
+///
+/// Queries can be parsed from their textual form with `parse`, or built with
+/// `literal`, `all` and `any` and the `!`, `&` and `|` operators.
+///
+/// # Example
+///
+/// ```
+/// use sliceslice::Query;
+///
+/// let query = Query::parse(r#"("error" OR "fatal") AND NOT "healthcheck""#).unwrap();
+///
+/// assert!(query.matches(b"fatal: out of memory"));
+/// assert!(!query.matches(b"error: healthcheck timed out"));
+/// assert!(!query.matches(b"info: started"));
+///
+/// let built = (Query::literal(b"error") | Query::literal(b"fatal")) & !Query::literal(b"healthcheck");
+/// assert!(built.matches(b"fatal: out of memory"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Query {
+    node: Node,
+    // Estimate of how likely the query is to match, from 0 to `u8::MAX`.
+    commonness: u8,
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Literal(Finder<Box<[u8]>>),
+    All(Box<[Query]>),
+    Any(Box<[Query]>),
+    Not(Box<Query>),
+}
+
+impl Query {
+    /// Creates a query matching the haystacks which contain `needle`.
+    pub fn literal(needle: &[u8]) -> Self {
+        let position = rarest_position(needle);
+
+        Self {
+            node: Node::Literal(Finder::with_position(needle.into(), position)),
+            commonness: needle_rank(needle),
+        }
+    }
+
+    /// Creates a query matching the haystacks matched by all of `queries`,
+    /// which matches all haystacks if there are none.
+    pub fn all<I: IntoIterator<Item = Query>>(queries: I) -> Self {
+        let mut queries = flatten(queries, |node| matches!(node, Node::All(_)));
+        queries.sort_by_key(|query| query.commonness);
+
+        Self {
+            commonness: queries.first().map_or(u8::MAX, |query| query.commonness),
+            node: Node::All(queries.into_boxed_slice()),
+        }
+    }
+
+    /// Creates a query matching the haystacks matched by any of `queries`,
+    /// which matches no haystacks if there are none.
+    pub fn any<I: IntoIterator<Item = Query>>(queries: I) -> Self {
+        let mut queries = flatten(queries, |node| matches!(node, Node::Any(_)));
+        queries.sort_by_key(|query| u8::MAX - query.commonness);
+
+        Self {
+            commonness: queries.first().map_or(0, |query| query.commonness),
+            node: Node::Any(queries.into_boxed_slice()),
+        }
+    }
+
+    /// Parses a query from its textual form.
+    ///
+    /// Literals are enclosed in double quotes, within which a backslash makes
+    /// the following character part of the literal. They are combined with
+    /// the `NOT`, `AND` and `OR` operators, from highest to lowest precedence,
+    /// and parentheses. Operators must be uppercase and are separated from
+    /// their operands by whitespace or parentheses.
+    pub fn parse(expression: &str) -> Result<Self, QueryError> {
+        let mut parser = Parser {
+            input: expression.as_bytes(),
+            offset: 0,
+        };
+
+        let query = parser.parse_any()?;
+        match parser.next_token()? {
+            None => Ok(query),
+            Some((offset, _)) => Err(QueryError {
+                offset,
+                kind: QueryErrorKind::UnexpectedToken,
+            }),
+        }
+    }
+
+    /// Returns `true` if `haystack` matches the query.
+    pub fn matches(&self, haystack: &[u8]) -> bool {
+        match &self.node {
+            Node::Literal(finder) => finder.search_in(haystack),
+            Node::All(queries) => queries.iter().all(|query| query.matches(haystack)),
+            Node::Any(queries) => queries.iter().any(|query| query.matches(haystack)),
+            Node::Not(query) => !query.matches(haystack),
+        }
+    }
+}
+
+/// Collects `queries`, replacing those for which `is_nested` returns `true`
+/// with their operands.
+fn flatten<I: IntoIterator<Item = Query>>(queries: I, is_nested: fn(&Node) -> bool) -> Vec<Query> {
+    let mut flattened = Vec::new();
+    for query in queries {
+        match query.node {
+            Node::All(queries) | Node::Any(queries) if is_nested(&query.node) => {
+                flattened.extend(queries.into_vec())
+            }
+            _ => flattened.push(query),
+        }
+    }
+
+    flattened
+}
+
+impl ops::Not for Query {
+    type Output = Query;
+
+    fn not(self) -> Query {
+        match self.node {
+            Node::Not(query) => *query,
+            _ => Query {
+                commonness: u8::MAX - self.commonness,
+                node: Node::Not(Box::new(self)),
+            },
+        }
+    }
+}
+
+impl ops::BitAnd for Query {
+    type Output = Query;
+
+    fn bitand(self, rhs: Query) -> Query {
+        Query::all([self, rhs])
+    }
+}
+
+impl ops::BitOr for Query {
+    type Output = Query;
+
+    fn bitor(self, rhs: Query) -> Query {
+        Query::any([self, rhs])
+    }
+}
+
+/// Error returned by `Query::parse` for invalid expressions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueryError {
+    offset: usize,
+    kind: QueryErrorKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum QueryErrorKind {
+    UnexpectedEnd,
+    UnexpectedToken,
+    UnterminatedLiteral,
+}
+
+impl QueryError {
+    /// Returns the offset in the expression at which the error was detected.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self.kind {
+            QueryErrorKind::UnexpectedEnd => "unexpected end of query",
+            QueryErrorKind::UnexpectedToken => "unexpected token in query",
+            QueryErrorKind::UnterminatedLiteral => "unterminated literal in query",
+        };
+
+        write!(f, "{} at offset {}", message, self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for QueryError {}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Literal(Vec<u8>),
+    Not,
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+/// Recursive descent parser for the textual form of queries.
+struct Parser<'a> {
+    input: &'a [u8],
+    offset: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, offset: usize, kind: QueryErrorKind) -> QueryError {
+        QueryError { offset, kind }
+    }
+
+    /// Returns the next token along with its offset, or `None` at the end of
+    /// the input.
+    fn next_token(&mut self) -> Result<Option<(usize, Token)>, QueryError> {
+        while self
+            .input
+            .get(self.offset)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.offset += 1;
+        }
+
+        let start = self.offset;
+        let Some(&byte) = self.input.get(start) else {
+            return Ok(None);
+        };
+
+        let token = match byte {
+            b'(' => Token::Open,
+            b')' => Token::Close,
+            b'"' => {
+                let unterminated = self.error(start, QueryErrorKind::UnterminatedLiteral);
+
+                let mut literal = Vec::new();
+                let mut bytes = self.input[start + 1..].iter();
+                loop {
+                    match bytes.next().ok_or(unterminated)? {
+                        b'"' => break,
+                        b'\\' => literal.push(*bytes.next().ok_or(unterminated)?),
+                        &byte => literal.push(byte),
+                    }
+                }
+
+                self.offset = self.input.len() - bytes.as_slice().len();
+                return Ok(Some((start, Token::Literal(literal))));
+            }
+            _ => {
+                let end = self.input[start..]
+                    .iter()
+                    .position(|byte| !byte.is_ascii_alphanumeric())
+                    .map_or(self.input.len(), |length| start + length);
+
+                let token = match &self.input[start..end] {
+                    b"NOT" => Token::Not,
+                    b"AND" => Token::And,
+                    b"OR" => Token::Or,
+                    _ => return Err(self.error(start, QueryErrorKind::UnexpectedToken)),
+                };
+
+                self.offset = end;
+                return Ok(Some((start, token)));
+            }
+        };
+
+        self.offset += 1;
+        Ok(Some((start, token)))
+    }
+
+    /// Returns the next token without consuming it.
+    fn peek_token(&mut self) -> Result<Option<Token>, QueryError> {
+        let offset = self.offset;
+        let token = self.next_token()?;
+        self.offset = offset;
+
+        Ok(token.map(|(_, token)| token))
+    }
+
+    fn parse_any(&mut self) -> Result<Query, QueryError> {
+        let mut queries = Vec::from([self.parse_all()?]);
+        while self.peek_token()? == Some(Token::Or) {
+            self.next_token()?;
+            queries.push(self.parse_all()?);
+        }
+
+        Ok(match queries.len() {
+            1 => queries.remove(0),
+            _ => Query::any(queries),
+        })
+    }
+
+    fn parse_all(&mut self) -> Result<Query, QueryError> {
+        let mut queries = Vec::from([self.parse_not()?]);
+        while self.peek_token()? == Some(Token::And) {
+            self.next_token()?;
+            queries.push(self.parse_not()?);
+        }
+
+        Ok(match queries.len() {
+            1 => queries.remove(0),
+            _ => Query::all(queries),
+        })
+    }
+
+    fn parse_not(&mut self) -> Result<Query, QueryError> {
+        match self.next_token()? {
+            Some((_, Token::Not)) => Ok(!self.parse_not()?),
+            Some((_, Token::Literal(literal))) => Ok(Query::literal(&literal)),
+            Some((_, Token::Open)) => {
+                let query = self.parse_any()?;
+                match self.next_token()? {
+                    Some((_, Token::Close)) => Ok(query),
+                    Some((offset, _)) => Err(self.error(offset, QueryErrorKind::UnexpectedToken)),
+                    None => Err(self.error(self.offset, QueryErrorKind::UnexpectedEnd)),
+                }
+            }
+            Some((offset, _)) => Err(self.error(offset, QueryErrorKind::UnexpectedToken)),
+            None => Err(self.error(self.offset, QueryErrorKind::UnexpectedEnd)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Node, Query, QueryError, QueryErrorKind};
+
+    const HAYSTACKS: [&[u8]; 6] = [
+        b"",
+        b"error: disk full",
+        b"fatal: out of memory",
+        b"error: healthcheck timed out",
+        b"info: healthcheck ok",
+        b"warning: \"quoted\" (parenthesized)",
+    ];
+
+    #[test]
+    fn query_parse() {
+        fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+            haystack
+                .windows(needle.len())
+                .any(|window| window == needle)
+        }
+
+        type Expected = fn(&[u8]) -> bool;
+
+        let cases: [(&str, Expected); 7] = [
+            (r#""error""#, |h| contains(h, b"error")),
+            (r#"NOT "error""#, |h| !contains(h, b"error")),
+            (r#""error" OR "fatal" AND "memory""#, |h| {
+                contains(h, b"error") || contains(h, b"fatal") && contains(h, b"memory")
+            }),
+            (r#"("error" OR "fatal") AND NOT "healthcheck""#, |h| {
+                (contains(h, b"error") || contains(h, b"fatal")) && !contains(h, b"healthcheck")
+            }),
+            (r#"NOT NOT ("ok" OR "out") AND ":""#, |h| {
+                (contains(h, b"ok") || contains(h, b"out")) && contains(h, b":")
+            }),
+            (r#""\"quoted\"" AND "(""#, |h| {
+                contains(h, b"\"quoted\"") && contains(h, b"(")
+            }),
+            (
+                r#"NOT ("error" AND "disk") AND NOT ("info" OR "warning") AND "o""#,
+                |h| {
+                    !(contains(h, b"info")
+                        || contains(h, b"warning")
+                        || contains(h, b"error") && contains(h, b"disk"))
+                        && contains(h, b"o")
+                },
+            ),
+        ];
+
+        for (expression, expected) in cases {
+            let query = Query::parse(expression).unwrap();
+            for haystack in HAYSTACKS {
+                assert_eq!(query.matches(haystack), expected(haystack), "{expression}");
+            }
+        }
+    }
+
+    #[test]
+    fn query_empty_operands() {
+        for haystack in HAYSTACKS {
+            assert!(Query::all([]).matches(haystack));
+            assert!(!Query::any([]).matches(haystack));
+            assert!(Query::literal(b"").matches(haystack));
+        }
+    }
+
+    #[test]
+    fn query_selectivity_order() {
+        let query = Query::literal(b"e") & (Query::literal(b"zq") & Query::literal(b"t"));
+        let Node::All(queries) = &query.node else {
+            panic!("expected a conjunction");
+        };
+
+        // Nested conjunctions are flattened and the rarest needle comes first.
+        let needles = queries
+            .iter()
+            .map(|query| match &query.node {
+                Node::Literal(finder) => &finder.needle()[..],
+                _ => panic!("expected a literal"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(needles, [&b"zq"[..], b"t", b"e"]);
+
+        let query = Query::literal(b"zq") | Query::literal(b"e") | !Query::literal(b" ");
+        let Node::Any(queries) = &query.node else {
+            panic!("expected a disjunction");
+        };
+        assert!(matches!(&queries[0].node, Node::Literal(finder) if &finder.needle()[..] == b"e"));
+        assert!(matches!(&queries[2].node, Node::Not(_)));
+    }
+
+    #[test]
+    fn query_invalid() {
+        for (expression, offset, kind) in [
+            ("", 0, QueryErrorKind::UnexpectedEnd),
+            ("  ", 2, QueryErrorKind::UnexpectedEnd),
+            (r#""error" AND"#, 11, QueryErrorKind::UnexpectedEnd),
+            (r#"("error""#, 8, QueryErrorKind::UnexpectedEnd),
+            (r#""error" "fatal""#, 8, QueryErrorKind::UnexpectedToken),
+            (r#""error" and "fatal""#, 8, QueryErrorKind::UnexpectedToken),
+            (r#""error" AND )"#, 12, QueryErrorKind::UnexpectedToken),
+            (r#"("error" "fatal")"#, 9, QueryErrorKind::UnexpectedToken),
+            (
+                r#""error" AND "fatal"#,
+                12,
+                QueryErrorKind::UnterminatedLiteral,
+            ),
+            (r#""error\""#, 0, QueryErrorKind::UnterminatedLiteral),
+            (r#""error\"#, 0, QueryErrorKind::UnterminatedLiteral),
+        ] {
+            assert_eq!(
+                Query::parse(expression).unwrap_err(),
+                QueryError { offset, kind },
+                "{expression}"
+            );
+        }
+    }
+}
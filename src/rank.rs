@@ -0,0 +1,342 @@
+//! Heuristic estimates of how common bytes and needles are in typical
+//! haystacks, based on a table ranking each byte value by frequency. They are
+//! only guesses, independent of any particular haystack:
+//!
+//! - `rarest_position` picks the second byte of the SIMD hash of a needle, as
+//!   in `memmem`, the `searcher!` macro and wildcard patterns;
+//! - `needle_rank` compares needles with one another, to decide which needle
+//!   drives `Finder::find_near` or in which order the operands of a `Query`
+//!   are evaluated.
+
+/// Heuristic rank of each byte value by how frequently it occurs in typical
+/// haystacks, where a higher rank means a more common byte. The table is taken
+/// from the `memchr` crate, which derived it from a corpus of text and binary
+/// data.
+const RANK: [u8; 256] = [
+    55,  // '\x00'
+    52,  // '\x01'
+    51,  // '\x02'
+    50,  // '\x03'
+    49,  // '\x04'
+    48,  // '\x05'
+    47,  // '\x06'
+    46,  // '\x07'
+    45,  // '\x08'
+    103, // '\t'
+    242, // '\n'
+    66,  // '\x0b'
+    67,  // '\x0c'
+    229, // '\r'
+    44,  // '\x0e'
+    43,  // '\x0f'
+    42,  // '\x10'
+    41,  // '\x11'
+    40,  // '\x12'
+    39,  // '\x13'
+    38,  // '\x14'
+    37,  // '\x15'
+    36,  // '\x16'
+    35,  // '\x17'
+    34,  // '\x18'
+    33,  // '\x19'
+    56,  // '\x1a'
+    32,  // '\x1b'
+    31,  // '\x1c'
+    30,  // '\x1d'
+    29,  // '\x1e'
+    28,  // '\x1f'
+    255, // ' '
+    148, // '!'
+    164, // '"'
+    149, // '#'
+    136, // '$'
+    160, // '%'
+    155, // '&'
+    173, // "'"
+    221, // '('
+    222, // ')'
+    134, // '*'
+    122, // '+'
+    232, // ','
+    202, // '-'
+    215, // '.'
+    224, // '/'
+    208, // '0'
+    220, // '1'
+    204, // '2'
+    187, // '3'
+    183, // '4'
+    179, // '5'
+    177, // '6'
+    168, // '7'
+    178, // '8'
+    200, // '9'
+    226, // ':'
+    195, // ';'
+    154, // '<'
+    184, // '='
+    174, // '>'
+    126, // '?'
+    120, // '@'
+    191, // 'A'
+    157, // 'B'
+    194, // 'C'
+    170, // 'D'
+    189, // 'E'
+    162, // 'F'
+    161, // 'G'
+    150, // 'H'
+    193, // 'I'
+    142, // 'J'
+    137, // 'K'
+    171, // 'L'
+    176, // 'M'
+    185, // 'N'
+    167, // 'O'
+    186, // 'P'
+    112, // 'Q'
+    175, // 'R'
+    192, // 'S'
+    188, // 'T'
+    156, // 'U'
+    140, // 'V'
+    143, // 'W'
+    123, // 'X'
+    133, // 'Y'
+    128, // 'Z'
+    147, // '['
+    138, // '\\'
+    146, // ']'
+    114, // '^'
+    223, // '_'
+    151, // '`'
+    249, // 'a'
+    216, // 'b'
+    238, // 'c'
+    236, // 'd'
+    253, // 'e'
+    227, // 'f'
+    218, // 'g'
+    230, // 'h'
+    247, // 'i'
+    135, // 'j'
+    180, // 'k'
+    241, // 'l'
+    233, // 'm'
+    246, // 'n'
+    244, // 'o'
+    231, // 'p'
+    139, // 'q'
+    245, // 'r'
+    243, // 's'
+    251, // 't'
+    235, // 'u'
+    201, // 'v'
+    196, // 'w'
+    240, // 'x'
+    214, // 'y'
+    152, // 'z'
+    182, // '{'
+    205, // '|'
+    181, // '}'
+    127, // '~'
+    27,  // '\x7f'
+    212, // '\x80'
+    211, // '\x81'
+    210, // '\x82'
+    213, // '\x83'
+    228, // '\x84'
+    197, // '\x85'
+    169, // '\x86'
+    159, // '\x87'
+    131, // '\x88'
+    172, // '\x89'
+    105, // '\x8a'
+    80,  // '\x8b'
+    98,  // '\x8c'
+    96,  // '\x8d'
+    97,  // '\x8e'
+    81,  // '\x8f'
+    207, // '\x90'
+    145, // '\x91'
+    116, // '\x92'
+    115, // '\x93'
+    144, // '\x94'
+    130, // '\x95'
+    153, // '\x96'
+    121, // '\x97'
+    107, // '\x98'
+    132, // '\x99'
+    109, // '\x9a'
+    110, // '\x9b'
+    124, // '\x9c'
+    111, // '\x9d'
+    82,  // '\x9e'
+    108, // '\x9f'
+    118, // '\xa0'
+    141, // '¡'
+    113, // '¢'
+    129, // '£'
+    119, // '¤'
+    125, // '¥'
+    165, // '¦'
+    117, // '§'
+    92,  // '¨'
+    106, // '©'
+    83,  // 'ª'
+    72,  // '«'
+    99,  // '¬'
+    93,  // '\xad'
+    65,  // '®'
+    79,  // '¯'
+    166, // '°'
+    237, // '±'
+    163, // '²'
+    199, // '³'
+    190, // '´'
+    225, // 'µ'
+    209, // '¶'
+    203, // '·'
+    198, // '¸'
+    217, // '¹'
+    219, // 'º'
+    206, // '»'
+    234, // '¼'
+    248, // '½'
+    158, // '¾'
+    239, // '¿'
+    255, // 'À'
+    255, // 'Á'
+    255, // 'Â'
+    255, // 'Ã'
+    255, // 'Ä'
+    255, // 'Å'
+    255, // 'Æ'
+    255, // 'Ç'
+    255, // 'È'
+    255, // 'É'
+    255, // 'Ê'
+    255, // 'Ë'
+    255, // 'Ì'
+    255, // 'Í'
+    255, // 'Î'
+    255, // 'Ï'
+    255, // 'Ð'
+    255, // 'Ñ'
+    255, // 'Ò'
+    255, // 'Ó'
+    255, // 'Ô'
+    255, // 'Õ'
+    255, // 'Ö'
+    255, // '×'
+    255, // 'Ø'
+    255, // 'Ù'
+    255, // 'Ú'
+    255, // 'Û'
+    255, // 'Ü'
+    255, // 'Ý'
+    255, // 'Þ'
+    255, // 'ß'
+    255, // 'à'
+    255, // 'á'
+    255, // 'â'
+    255, // 'ã'
+    255, // 'ä'
+    255, // 'å'
+    255, // 'æ'
+    255, // 'ç'
+    255, // 'è'
+    255, // 'é'
+    255, // 'ê'
+    255, // 'ë'
+    255, // 'ì'
+    255, // 'í'
+    255, // 'î'
+    255, // 'ï'
+    255, // 'ð'
+    255, // 'ñ'
+    255, // 'ò'
+    255, // 'ó'
+    255, // 'ô'
+    255, // 'õ'
+    255, // 'ö'
+    255, // '÷'
+    255, // 'ø'
+    255, // 'ù'
+    255, // 'ú'
+    255, // 'û'
+    255, // 'ü'
+    255, // 'ý'
+    255, // 'þ'
+    255, // 'ÿ'
+];
+
+/// Returns the rank of `byte`, where a higher rank means a more common byte.
+#[inline]
+pub const fn rank(byte: u8) -> u8 {
+    RANK[byte as usize]
+}
+
+/// Selects the `position` of the second byte used by the SIMD hash, picking
+/// the least common byte of `needle` according to `rank` and preferring the
+/// furthest such byte from the start on ties. The first byte is always part of
+/// the hash and is therefore not considered, unless `needle` has a length of
+/// one.
+pub const fn rarest_position(needle: &[u8]) -> usize {
+    if needle.len() <= 1 {
+        return 0;
+    }
+
+    let mut position = 1;
+    let mut i = 2;
+    while i < needle.len() {
+        if rank(needle[i]) <= rank(needle[position]) {
+            position = i;
+        }
+        i += 1;
+    }
+
+    position
+}
+
+/// Estimates how common `needle` is in typical haystacks as the `rank` of its
+/// least common byte, so that a lower value means a rarer needle. The empty
+/// needle, which occurs everywhere, gets the highest value.
+pub const fn needle_rank(needle: &[u8]) -> u8 {
+    let mut lowest = u8::MAX;
+    let mut i = 0;
+    while i < needle.len() {
+        if rank(needle[i]) < lowest {
+            lowest = rank(needle[i]);
+        }
+        i += 1;
+    }
+
+    lowest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{needle_rank, rarest_position};
+
+    #[test]
+    fn rarest_position_short() {
+        assert_eq!(rarest_position(b""), 0);
+        assert_eq!(rarest_position(b"x"), 0);
+        assert_eq!(rarest_position(b"xy"), 1);
+    }
+
+    #[test]
+    fn rarest_position_rare_byte() {
+        assert_eq!(rarest_position(b"Content-Length:"), 8);
+        assert_eq!(rarest_position(b"zzz"), 2);
+    }
+
+    #[test]
+    fn needle_rank_rarest_byte() {
+        assert_eq!(needle_rank(b""), u8::MAX);
+        assert_eq!(needle_rank(b"   "), u8::MAX);
+        assert!(needle_rank(b"aws_secret") < needle_rank(b"the"));
+        assert_eq!(needle_rank(b"zebra"), needle_rank(b"z"));
+    }
+}
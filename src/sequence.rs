@@ -0,0 +1,254 @@
+use crate::{Finder, Needle, NeedleWithSize};
+use alloc::boxed::Box;
+
+/// Searcher for a sequence of needles occurring in order, each one starting
+/// after the end of the previous one, like the regular expression `A.*B.*C`.
+///
+/// The occurrences reported are those of the first match, i.e. the one where
+/// the first needle occurs leftmost, then the second one, and so on. A maximum
+/// gap may be specified, limiting the number of bytes between the end of each
+/// needle and the start of the next one. Every position of each needle is
+/// looked for at most once, so searching stays linear even when the maximum
+/// gap requires trying several occurrences.
+///
+/// # Example
+///
+/// ```
+/// use sliceslice::SequenceSearcher;
+///
+/// let searcher = SequenceSearcher::new([&b"foo"[..], b"bar"]);
+/// let mut offsets = [0; 2];
+///
+/// assert!(searcher.find_in(b"foo baz foo bar", &mut offsets));
+/// assert_eq!(offsets, [0, 12]);
+///
+/// let searcher = SequenceSearcher::with_max_gap([&b"foo"[..], b"bar"], 1);
+///
+/// assert!(searcher.find_in(b"foo baz foo bar", &mut offsets));
+/// assert_eq!(offsets, [8, 12]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SequenceSearcher<N: Needle> {
+    finders: Box<[Finder<N>]>,
+    max_gap: Option<usize>,
+}
+
+impl<N: Needle> SequenceSearcher<N> {
+    /// Creates a new searcher for `needles`, in order, without any limit on
+    /// the gaps between them.
+    pub fn new<I: IntoIterator<Item = N>>(needles: I) -> Self {
+        Self {
+            finders: needles.into_iter().map(Finder::new).collect(),
+            max_gap: None,
+        }
+    }
+
+    /// Same as `new` but allows at most `max_gap` bytes between the end of each
+    /// needle and the start of the next one.
+    pub fn with_max_gap<I: IntoIterator<Item = N>>(needles: I, max_gap: usize) -> Self {
+        Self {
+            finders: needles.into_iter().map(Finder::new).collect(),
+            max_gap: Some(max_gap),
+        }
+    }
+
+    /// Returns the number of needles in the sequence.
+    pub fn len(&self) -> usize {
+        self.finders.len()
+    }
+
+    /// Returns `true` if the sequence contains no needles, in which case it is
+    /// found in all haystacks.
+    pub fn is_empty(&self) -> bool {
+        self.finders.is_empty()
+    }
+
+    /// Performs a search for the sequence of needles within `haystack`,
+    /// returning `true` if it is found.
+    pub fn search_in(&self, haystack: &[u8]) -> bool {
+        let mut offsets = [0; 8];
+        match self.finders.len() <= offsets.len() {
+            true => self.find_in(haystack, &mut offsets[..self.finders.len()]),
+            false => self.find_in(haystack, &mut alloc::vec![0; self.finders.len()]),
+        }
+    }
+
+    /// Performs a search for the sequence of needles within `haystack`. If it
+    /// is found, returns `true` and writes the offset of the occurrence of
+    /// each needle to `offsets`, otherwise returns `false` and leaves
+    /// `offsets` unspecified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offsets.len()` is not the number of needles.
+    pub fn find_in(&self, haystack: &[u8], offsets: &mut [usize]) -> bool {
+        assert_eq!(self.finders.len(), offsets.len(), "output length mismatch");
+
+        if self.finders.is_empty() {
+            return true;
+        }
+
+        // While searching, `offsets[i]` holds the first position of needle `i`
+        // not tried yet. Windows only move forward as previous needles are
+        // found further, so positions which failed before are never retried.
+        offsets.fill(0);
+
+        let mut i = 0;
+        loop {
+            let size = self.finders[i].needle().size();
+            let (start, end) = match i.checked_sub(1) {
+                Some(previous) => {
+                    let start = offsets[previous] + self.finders[previous].needle().size();
+                    let end = match self.max_gap {
+                        Some(max_gap) => start.saturating_add(max_gap).saturating_add(size),
+                        None => haystack.len(),
+                    };
+                    (start, end.min(haystack.len()))
+                }
+                None => (0, haystack.len()),
+            };
+
+            let from = start.max(offsets[i]);
+            let found = match from <= end {
+                true => self.finders[i].find_in(&haystack[from..end]),
+                false => None,
+            };
+
+            match found {
+                Some(found) => {
+                    offsets[i] = from + found;
+                    if i + 1 == offsets.len() {
+                        return true;
+                    }
+                    i += 1;
+                }
+                None => {
+                    // All positions in the window were tried.
+                    offsets[i] = from.max((end + 1).saturating_sub(size));
+                    match i.checked_sub(1) {
+                        Some(previous) => {
+                            i = previous;
+                            offsets[i] += 1;
+                        }
+                        None => return false,
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SequenceSearcher;
+
+    /// Returns the offsets of the first match of `needles` in `haystack` found
+    /// by trying every combination of positions.
+    fn naive(
+        needles: &[&[u8]],
+        max_gap: Option<usize>,
+        haystack: &[u8],
+        previous_end: Option<usize>,
+    ) -> Option<Vec<usize>> {
+        let is_first = previous_end.is_none();
+        let Some((needle, rest)) = needles.split_first() else {
+            return Some(Vec::new());
+        };
+
+        // The first needle may start anywhere, the following ones must start
+        // after the end of the previous one.
+        let start = previous_end.unwrap_or(0);
+        let end = match max_gap {
+            Some(max_gap) if !is_first => (start + max_gap).min(haystack.len()),
+            _ => haystack.len(),
+        };
+
+        (start..=end).find_map(|offset| {
+            if !haystack[offset..].starts_with(needle) {
+                return None;
+            }
+
+            let mut offsets = naive(rest, max_gap, haystack, Some(offset + needle.len()))?;
+            offsets.insert(0, offset);
+            Some(offsets)
+        })
+    }
+
+    #[test]
+    fn sequence_naive() {
+        let haystacks = [
+            &b""[..],
+            b"a",
+            b"abc",
+            b"aaaaaaaaaa",
+            b"foo bar baz foo qux bar foo",
+            b"abcabcabcabcabcabcabcabcabcabcabcabcabcabc",
+            b"ab..c...ab....c.ab.c",
+        ];
+        let sequences: [&[&[u8]]; 10] = [
+            &[],
+            &[b""],
+            &[b"a"],
+            &[b"a", b"a"],
+            &[b"a", b"", b"a", b"a"],
+            &[b"foo", b"bar"],
+            &[b"foo", b"bar", b"foo"],
+            &[b"bar", b"foo", b"qux"],
+            &[b"ab", b"c", b"ab", b"c"],
+            &[b"abc", b"cab"],
+        ];
+
+        for needles in sequences {
+            for max_gap in [None, Some(0), Some(1), Some(2), Some(4)] {
+                let searcher = match max_gap {
+                    Some(max_gap) => {
+                        SequenceSearcher::with_max_gap(needles.iter().copied(), max_gap)
+                    }
+                    None => SequenceSearcher::new(needles.iter().copied()),
+                };
+
+                for haystack in haystacks {
+                    let expected = naive(needles, max_gap, haystack, None);
+
+                    let mut offsets = vec![0; needles.len()];
+                    let found = searcher.find_in(haystack, &mut offsets);
+                    assert_eq!(
+                        found.then_some(offsets),
+                        expected,
+                        "{needles:?} {max_gap:?} {haystack:?}"
+                    );
+                    assert_eq!(searcher.search_in(haystack), expected.is_some());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sequence_backtracking() {
+        // The first occurrences of each needle do not lead to a match, which
+        // requires trying later ones at every level.
+        let haystack = b"a b a c b a b c d";
+        let searcher = SequenceSearcher::with_max_gap([&b"a"[..], b"b", b"c", b"d"], 1);
+
+        let mut offsets = [0; 4];
+        assert!(searcher.find_in(haystack, &mut offsets));
+        assert_eq!(offsets, [10, 12, 14, 16]);
+    }
+
+    #[test]
+    fn sequence_many() {
+        let needles = [&b"a"[..]; 10];
+        let searcher = SequenceSearcher::new(needles);
+
+        assert_eq!(searcher.len(), 10);
+        assert!(searcher.search_in(&[b'a'; 10]));
+        assert!(!searcher.search_in(&[b'a'; 9]));
+    }
+
+    #[test]
+    #[should_panic(expected = "output length mismatch")]
+    fn sequence_invalid_output() {
+        let searcher = SequenceSearcher::new([&b"foo"[..], b"bar"]);
+        searcher.find_in(b"foo bar", &mut [0; 3]);
+    }
+}
@@ -1,8 +1,13 @@
 #![allow(clippy::missing_safety_doc)]
 
-use crate::{Needle, NeedleWithSize, Searcher, Vector, VectorHash};
+use crate::{
+    linear_budget, search_in_batch_bits_with, search_in_batch_with, Aligned, Budget,
+    BudgetExceeded, ByteClass, CompiledNeedle, Filter, Needle, NeedleWithSize, Scan, Searcher,
+    Unfiltered, Unlimited, Vector, VectorHash, WordBoundary,
+};
+use core::ops::Range;
 #[cfg(feature = "stdsimd")]
-use std::simd::*;
+use core::simd::*;
 
 trait ToFixedBitMask: Sized {
     fn to_fixed_bitmask(self) -> u32;
@@ -35,7 +40,7 @@ where
 
     #[inline]
     unsafe fn load(a: *const u8) -> Self {
-        std::ptr::read_unaligned(a as *const Self)
+        core::ptr::read_unaligned(a as *const Self)
     }
 
     #[inline]
@@ -134,11 +139,96 @@ impl<N: Needle> StdSimdSearcher<N> {
         }
     }
 
-    /// Inlined version of `search_in` for hot call sites.
+    /// Creates a new searcher from a `CompiledNeedle`, using its needle and
+    /// `position`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the needle is empty or if the associated `SIZE` constant does
+    /// not correspond to the actual size of the needle.
+    pub fn from_compiled(compiled: CompiledNeedle<N>) -> Self {
+        let (needle, position) = compiled.into_parts();
+        Self::with_position(needle, position)
+    }
+
     #[inline]
-    pub fn inlined_search_in(&self, haystack: &[u8]) -> bool {
+    fn scan<B: Budget, F: Filter>(&self, haystack: &[u8], budget: &mut B, filter: &F) -> Scan {
+        self.scan_in(haystack, 0..haystack.len(), budget, filter)
+    }
+
+    /// Same as `scan` but only reports occurrences within `haystack[range]`,
+    /// while still loading chunks from the rest of `haystack`. Offsets are
+    /// relative to the start of `haystack`.
+    #[inline]
+    fn scan_in<B: Budget, F: Filter>(
+        &self,
+        haystack: &[u8],
+        range: Range<usize>,
+        budget: &mut B,
+        filter: &F,
+    ) -> Scan {
+        if haystack.len() <= self.needle.size() {
+            return if range == (0..haystack.len()) && haystack == self.needle.as_bytes() {
+                Scan::Found(0)
+            } else {
+                Scan::NotFound
+            };
+        }
+
+        let end = haystack.len() - self.needle.size() + 1;
+        let positions = match (range.end + 1).checked_sub(self.needle.size()) {
+            Some(limit) if limit > range.start => range.start..limit,
+            _ => return Scan::NotFound,
+        };
+
+        if end < Simd2::LANES {
+            unreachable!();
+        } else if end < Simd4::LANES {
+            let hash = from_hash::<32, 2>(&self.simd32_hash);
+            unsafe {
+                self.vector_search_in_default_version(
+                    haystack, positions, end, &hash, budget, filter,
+                )
+            }
+        } else if end < Simd8::LANES {
+            let hash = from_hash::<32, 4>(&self.simd32_hash);
+            unsafe {
+                self.vector_search_in_default_version(
+                    haystack, positions, end, &hash, budget, filter,
+                )
+            }
+        } else if end < Simd16::LANES {
+            let hash = from_hash::<32, 8>(&self.simd32_hash);
+            unsafe {
+                self.vector_search_in_default_version(
+                    haystack, positions, end, &hash, budget, filter,
+                )
+            }
+        } else if end < Simd32::LANES {
+            let hash = from_hash::<32, 16>(&self.simd32_hash);
+            unsafe {
+                self.vector_search_in_default_version(
+                    haystack, positions, end, &hash, budget, filter,
+                )
+            }
+        } else {
+            unsafe {
+                self.vector_search_in_default_version(
+                    haystack,
+                    positions,
+                    end,
+                    &self.simd32_hash,
+                    budget,
+                    filter,
+                )
+            }
+        }
+    }
+
+    #[inline]
+    fn rscan(&self, haystack: &[u8]) -> Option<usize> {
         if haystack.len() <= self.needle.size() {
-            return haystack == self.needle.as_bytes();
+            return (haystack == self.needle.as_bytes()).then_some(0);
         }
 
         let end = haystack.len() - self.needle.size() + 1;
@@ -147,39 +237,224 @@ impl<N: Needle> StdSimdSearcher<N> {
             unreachable!();
         } else if end < Simd4::LANES {
             let hash = from_hash::<32, 2>(&self.simd32_hash);
-            unsafe { self.vector_search_in_default_version(haystack, end, &hash) }
+            unsafe { self.vector_rsearch_in_default_version(haystack, end, &hash) }
         } else if end < Simd8::LANES {
             let hash = from_hash::<32, 4>(&self.simd32_hash);
-            unsafe { self.vector_search_in_default_version(haystack, end, &hash) }
+            unsafe { self.vector_rsearch_in_default_version(haystack, end, &hash) }
         } else if end < Simd16::LANES {
             let hash = from_hash::<32, 8>(&self.simd32_hash);
-            unsafe { self.vector_search_in_default_version(haystack, end, &hash) }
+            unsafe { self.vector_rsearch_in_default_version(haystack, end, &hash) }
         } else if end < Simd32::LANES {
             let hash = from_hash::<32, 16>(&self.simd32_hash);
-            unsafe { self.vector_search_in_default_version(haystack, end, &hash) }
+            unsafe { self.vector_rsearch_in_default_version(haystack, end, &hash) }
         } else {
-            unsafe { self.vector_search_in_default_version(haystack, end, &self.simd32_hash) }
+            unsafe { self.vector_rsearch_in_default_version(haystack, end, &self.simd32_hash) }
         }
     }
 
+    /// Inlined version of `search_in` for hot call sites.
+    #[inline]
+    pub fn inlined_search_in(&self, haystack: &[u8]) -> bool {
+        self.scan(haystack, &mut Unlimited, &Unfiltered).is_found()
+    }
+
     /// Performs a substring search for the `needle` within `haystack`.
     pub fn search_in(&self, haystack: &[u8]) -> bool {
         self.inlined_search_in(haystack)
     }
+
+    /// Performs a substring search for the `needle` within each of `haystacks`,
+    /// writing the results to the corresponding elements of `out`. This avoids
+    /// the overhead of a call per haystack, which dominates for short
+    /// haystacks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `haystacks` and `out` have different lengths.
+    pub fn search_in_batch(&self, haystacks: &[&[u8]], out: &mut [bool]) {
+        search_in_batch_with(haystacks, out, |haystack| self.inlined_search_in(haystack))
+    }
+
+    /// Same as `search_in_batch` but writes the results as a bitset, where bit
+    /// `i % 64` of `out[i / 64]` is set if and only if `haystacks[i]` contains
+    /// the `needle`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out.len()` is not `haystacks.len().div_ceil(64)`.
+    pub fn search_in_batch_bits(&self, haystacks: &[&[u8]], out: &mut [u64]) {
+        search_in_batch_bits_with(haystacks, out, |haystack| self.inlined_search_in(haystack))
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack`, if any.
+    pub fn find_in(&self, haystack: &[u8]) -> Option<usize> {
+        self.scan(haystack, &mut Unlimited, &Unfiltered).into()
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` starting at or after `start`, if any. The offset is relative
+    /// to the start of `haystack`, which allows resuming a search after a
+    /// previous occurrence.
+    ///
+    /// Unlike searching the subslice, the bytes of `haystack` before `start`
+    /// may be read, so that the SIMD scan keeps using full vectors instead of
+    /// falling back to narrower ones near the end of `haystack`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is greater than the length of `haystack`.
+    pub fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize> {
+        // Only check that `start` is in bounds.
+        let _ = &haystack[start..];
+        self.scan_in(haystack, start..haystack.len(), &mut Unlimited, &Unfiltered)
+            .into()
+    }
+
+    /// Performs a substring search for the `needle` within `haystack[range]`.
+    ///
+    /// Unlike searching the subslice, the bytes of `haystack` after the end of
+    /// `range` may be read, so that the SIMD scan keeps using full vectors
+    /// instead of falling back to narrower ones near the end of the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of `haystack`.
+    pub fn search_in_range(&self, haystack: &[u8], range: Range<usize>) -> bool {
+        // Only check that the range is in bounds.
+        let _ = &haystack[range.clone()];
+        self.scan_in(haystack, range, &mut Unlimited, &Unfiltered)
+            .is_found()
+    }
+
+    /// Returns the offset of the last occurrence of the `needle` within
+    /// `haystack`, if any.
+    pub fn rfind_in(&self, haystack: &[u8]) -> Option<usize> {
+        self.rscan(haystack)
+    }
+
+    /// Returns the offset of the first position in `haystack` where the
+    /// `needle` could start, judging only from the two bytes used by the SIMD
+    /// hash. The rest of the needle is not verified, but positions where the
+    /// needle would not fit in `haystack` are never returned.
+    pub fn find_candidate_in(&self, haystack: &[u8]) -> Option<usize> {
+        if haystack.len() <= self.needle.size() {
+            return self.short_candidate(haystack);
+        }
+
+        self.scan(haystack, &mut 0, &Unfiltered).into_candidate()
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` which is a whole word, i.e. which is neither preceded nor
+    /// followed by a byte of `class`, if any. Occurrences which are not whole
+    /// words are skipped without interrupting the SIMD scan.
+    pub fn find_word_in(&self, haystack: &[u8], class: &ByteClass) -> Option<usize> {
+        self.find_word_at(haystack, 0, class)
+    }
+
+    /// Same as `find_word_in` but only reports occurrences starting at or
+    /// after `start`, like `find_at`. The byte before `start` is still checked
+    /// for whole words, so that a search resumed past a previous occurrence
+    /// reports the same occurrences as a search of the whole `haystack`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is greater than the length of `haystack`.
+    pub fn find_word_at(&self, haystack: &[u8], start: usize, class: &ByteClass) -> Option<usize> {
+        // Only check that `start` is in bounds.
+        let _ = &haystack[start..];
+        let filter = WordBoundary::new(haystack, self.needle.size(), class);
+        self.scan_in(haystack, start..haystack.len(), &mut Unlimited, &filter)
+            .into()
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` at a multiple of `stride`, if any, such as the offsets of
+    /// fields in a table of aligned records. Candidates at other offsets are
+    /// masked out before being verified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    pub fn find_aligned_in(&self, haystack: &[u8], stride: usize) -> Option<usize> {
+        self.scan(haystack, &mut Unlimited, &Aligned::new(stride))
+            .into()
+    }
+
+    /// Same as `search_in` but with a worst-case running time linear in the
+    /// lengths of `haystack` and `needle`, falling back to the Two-Way
+    /// algorithm once too many candidate verifications have failed.
+    pub fn search_in_linear(&self, haystack: &[u8]) -> bool {
+        let mut budget = linear_budget(haystack.len(), self.needle.size());
+        self.scan(haystack, &mut budget, &Unfiltered)
+            .finish_linear(haystack, self.needle.as_bytes())
+    }
+
+    /// Same as `search_in` but gives up after `max_verifications` candidate
+    /// positions have been compared against the needle.
+    ///
+    /// This bounds the work spent on inputs where the SIMD filter lets through
+    /// many false positives, returning `BudgetExceeded` instead of stalling.
+    pub fn search_in_with_budget(
+        &self,
+        haystack: &[u8],
+        max_verifications: usize,
+    ) -> Result<bool, BudgetExceeded> {
+        let mut budget = max_verifications;
+        self.scan(haystack, &mut budget, &Unfiltered).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::StdSimdSearcher;
+    use crate::BudgetExceeded;
 
     impl crate::tests::TestSearcher for StdSimdSearcher<&[u8]> {
-        fn with_position(needle: &'static [u8], position: usize) -> StdSimdSearcher<&[u8]> {
+        fn with_position(needle: &'static [u8], position: usize) -> StdSimdSearcher<&'static [u8]> {
             StdSimdSearcher::with_position(needle, position)
         }
 
         fn search_in(&self, haystack: &[u8]) -> bool {
             StdSimdSearcher::search_in(self, haystack)
         }
+
+        fn search_in_linear(&self, haystack: &[u8]) -> bool {
+            StdSimdSearcher::search_in_linear(self, haystack)
+        }
+
+        fn search_in_with_budget(
+            &self,
+            haystack: &[u8],
+            max_verifications: usize,
+        ) -> Result<bool, BudgetExceeded> {
+            StdSimdSearcher::search_in_with_budget(self, haystack, max_verifications)
+        }
+
+        fn find_in(&self, haystack: &[u8]) -> Option<usize> {
+            StdSimdSearcher::find_in(self, haystack)
+        }
+
+        fn rfind_in(&self, haystack: &[u8]) -> Option<usize> {
+            StdSimdSearcher::rfind_in(self, haystack)
+        }
+
+        fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize> {
+            StdSimdSearcher::find_at(self, haystack, start)
+        }
+
+        fn search_in_range(&self, haystack: &[u8], range: core::ops::Range<usize>) -> bool {
+            StdSimdSearcher::search_in_range(self, haystack, range)
+        }
+
+        fn search_in_batch(&self, haystacks: &[&[u8]], out: &mut [bool]) {
+            StdSimdSearcher::search_in_batch(self, haystacks, out)
+        }
+
+        fn search_in_batch_bits(&self, haystacks: &[&[u8]], out: &mut [u64]) {
+            StdSimdSearcher::search_in_batch_bits(self, haystacks, out)
+        }
     }
 
     crate::generate_tests!(std_simd_searcher, StdSimdSearcher);
@@ -0,0 +1,289 @@
+use crate::{Finder, Needle, NeedleWithSize};
+use alloc::boxed::Box;
+
+/// Push-based searcher for a needle within a stream of bytes received in
+/// chunks of arbitrary sizes, such as the body of an HTTP request handed to a
+/// callback piece by piece.
+///
+/// Between chunks, the matcher only remembers the length of the longest prefix
+/// of the needle which is a suffix of the bytes seen so far, so that the bytes
+/// themselves need not be kept. Occurrences straddling the boundaries between
+/// chunks are found by running the Knuth-Morris-Pratt automaton over the
+/// start of each chunk, while the rest of the chunk is searched using a
+/// `Finder`. All occurrences are reported, including overlapping ones, with
+/// their offsets from the start of the stream.
+///
+/// # Example
+///
+/// ```
+/// use sliceslice::StreamingMatcher;
+///
+/// let mut matcher = StreamingMatcher::new(&b"needle"[..]);
+///
+/// assert_eq!(matcher.feed(b"hay hay nee").collect::<Vec<_>>(), []);
+/// assert_eq!(matcher.feed(b"dle hay nee").collect::<Vec<_>>(), [8]);
+/// assert_eq!(matcher.feed(b"dleneedle").collect::<Vec<_>>(), [19, 25]);
+/// assert_eq!(matcher.finish(), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StreamingMatcher<N: Needle> {
+    finder: Finder<N>,
+    // Length of the longest proper border of each prefix of the needle, by
+    // length of the prefix.
+    failure: Box<[usize]>,
+    // Length of the longest prefix of the needle which is a suffix of the
+    // bytes seen so far, always shorter than the needle.
+    state: usize,
+    position: u64,
+}
+
+impl<N: Needle> StreamingMatcher<N> {
+    /// Creates a new matcher for `needle`, at the start of a stream.
+    pub fn new(needle: N) -> Self {
+        let finder = Finder::new(needle);
+
+        let needle = finder.needle().as_bytes();
+        let mut failure = alloc::vec![0; needle.len() + 1];
+        for i in 1..needle.len() {
+            failure[i + 1] = advance(needle, &failure, failure[i], needle[i]);
+        }
+
+        Self {
+            finder,
+            failure: failure.into_boxed_slice(),
+            state: 0,
+            position: 0,
+        }
+    }
+
+    /// Returns the number of bytes fed since the start of the stream.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Pushes the next `chunk` of the stream, returning an iterator over the
+    /// offsets from the start of the stream of the occurrences of the needle
+    /// ending within `chunk`.
+    ///
+    /// The matcher moves past the whole chunk when the iterator is dropped,
+    /// however many occurrences it has yielded, so that occurrences straddling
+    /// this chunk and the following ones are still found. Occurrences ending
+    /// within `chunk` which the iterator has not yielded are not reported
+    /// later on.
+    pub fn feed<'m, 'c>(&'m mut self, chunk: &'c [u8]) -> Feed<'m, 'c, N> {
+        let state = self.state;
+        let position = self.position;
+
+        Feed {
+            matcher: self,
+            chunk,
+            position,
+            state,
+            straddling: 0,
+            offset: 0,
+        }
+    }
+
+    /// Ends the stream, returning the offset of the occurrence of the needle
+    /// at its very end if the needle is empty, and resets the matcher so that
+    /// it can be fed another stream.
+    pub fn finish(&mut self) -> Option<u64> {
+        let end = self.position;
+
+        self.state = 0;
+        self.position = 0;
+
+        self.finder.needle().as_bytes().is_empty().then_some(end)
+    }
+
+    /// Runs the automaton from `state` over `bytes` and returns the final
+    /// state, skipping past the occurrences of the needle.
+    fn run(&self, mut state: usize, bytes: &[u8]) -> usize {
+        let needle = self.finder.needle().as_bytes();
+
+        for &byte in bytes {
+            state = advance(needle, &self.failure, state, byte);
+            if state == needle.len() {
+                state = self.failure[state];
+            }
+        }
+
+        state
+    }
+}
+
+/// Returns the length of the longest prefix of `needle` which is a suffix of
+/// the prefix of length `state` followed by `byte`.
+#[inline]
+fn advance(needle: &[u8], failure: &[usize], mut state: usize, byte: u8) -> usize {
+    while state > 0 && needle[state] != byte {
+        state = failure[state];
+    }
+
+    match needle[state] == byte {
+        true => state + 1,
+        false => 0,
+    }
+}
+
+/// Iterator over the offsets of the occurrences of a needle ending within a
+/// chunk of a stream, created by `StreamingMatcher::feed`.
+#[derive(Debug)]
+pub struct Feed<'m, 'c, N: Needle> {
+    matcher: &'m mut StreamingMatcher<N>,
+    chunk: &'c [u8],
+    // Offset of the chunk from the start of the stream.
+    position: u64,
+    // State of the automaton before the byte at offset `straddling` of the
+    // chunk, which is run over the bytes where occurrences straddling the
+    // previous chunks can end.
+    state: usize,
+    straddling: usize,
+    // Offset within the chunk from which the next occurrence starting in it is
+    // searched.
+    offset: usize,
+}
+
+impl<N: Needle> Iterator for Feed<'_, '_, N> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let size = self.matcher.finder.needle().size();
+
+        // Occurrences starting before the chunk end within its first
+        // `size - 1` bytes.
+        let end = size.saturating_sub(1).min(self.chunk.len());
+        while self.state > 0 && self.straddling < end {
+            let needle = self.matcher.finder.needle().as_bytes();
+            let byte = self.chunk[self.straddling];
+            self.state = advance(needle, &self.matcher.failure, self.state, byte);
+            self.straddling += 1;
+
+            if self.state == size {
+                self.state = self.matcher.failure[size];
+                return Some(self.position + self.straddling as u64 - size as u64);
+            }
+        }
+
+        let rest = self.chunk.get(self.offset..)?;
+        let start = self.offset + self.matcher.finder.find_in(rest)?;
+        if start == self.chunk.len() {
+            // The empty needle at the end of the chunk is reported along with
+            // the next one.
+            return None;
+        }
+
+        self.offset = start + 1;
+        Some(self.position + start as u64)
+    }
+}
+
+impl<N: Needle> Drop for Feed<'_, '_, N> {
+    fn drop(&mut self) {
+        let size = self.matcher.finder.needle().size();
+
+        // The longest prefix of the needle which is a suffix of the stream
+        // fits within its last `size - 1` bytes, so the automaton only runs
+        // over those from the state reached so far.
+        let rest = &self.chunk[self.straddling..];
+        self.matcher.state = match size.checked_sub(1) {
+            None => 0,
+            Some(tail) if rest.len() >= tail => self.matcher.run(0, &rest[rest.len() - tail..]),
+            Some(_) => self.matcher.run(self.state, rest),
+        };
+        self.matcher.position += self.chunk.len() as u64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StreamingMatcher;
+
+    #[test]
+    fn streaming_naive() {
+        let data = b"abababcabcababcabababcabdabcabdabcabdaaaaaaabcabcabdabcabdabab";
+
+        for needle in [
+            &b""[..],
+            b"a",
+            b"aa",
+            b"aba",
+            b"abab",
+            b"abcabd",
+            b"abcabdabcabd",
+            b"xyz",
+            b"aaaaaaabcabcabdabcabdababx",
+        ] {
+            let expected = (0..=data.len() - needle.len().min(data.len()))
+                .filter(|&i| data[i..].starts_with(needle))
+                .map(|i| i as u64)
+                .collect::<Vec<_>>();
+
+            let mut matcher = StreamingMatcher::new(needle);
+            for sizes in [&[1][..], &[2], &[3], &[5, 1, 2], &[7, 0], &[64]] {
+                let mut found = Vec::new();
+                let mut rest = &data[..];
+                for &size in sizes.iter().cycle() {
+                    let (chunk, next) = rest.split_at(size.min(rest.len()));
+                    found.extend(matcher.feed(chunk));
+                    rest = next;
+
+                    if rest.is_empty() {
+                        break;
+                    }
+                }
+
+                assert_eq!(matcher.position(), data.len() as u64);
+                found.extend(matcher.finish());
+                assert_eq!(found, expected, "{needle:?} {sizes:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn streaming_partially_consumed() {
+        let data = b"abababcabcababcabababcabdabcabdabcabdaaaaaaabcabcabdabcabdabab";
+
+        for needle in [&b"a"[..], b"aba", b"abab", b"abcabd", b"abcabdabcabd"] {
+            let ends = (0..=data.len() - needle.len())
+                .filter(|&i| data[i..].starts_with(needle))
+                .map(|i| (i + needle.len(), i as u64))
+                .collect::<Vec<_>>();
+
+            let mut matcher = StreamingMatcher::new(needle);
+            for size in [1, 2, 3, 5, 7] {
+                for taken in [0, 1, 2] {
+                    // Each chunk only reports the occurrences ending within
+                    // it, whatever was left unconsumed in the previous ones.
+                    for (i, chunk) in data.chunks(size).enumerate() {
+                        let end = i * size..i * size + chunk.len();
+                        let expected = ends
+                            .iter()
+                            .filter(|&&(last, _)| end.start < last && last <= end.end)
+                            .map(|&(_, start)| start)
+                            .take(taken)
+                            .collect::<Vec<_>>();
+
+                        let found = matcher.feed(chunk).take(taken).collect::<Vec<_>>();
+                        assert_eq!(found, expected, "{needle:?} {size} {taken} {i}");
+                    }
+
+                    assert_eq!(matcher.finish(), None);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn streaming_dropped_iterator() {
+        let mut matcher = StreamingMatcher::new(&b"abcd"[..]);
+
+        // Dropping the iterator skips the occurrences in the chunk, but not
+        // those straddling the following ones.
+        let _ = matcher.feed(b"abcdab");
+        assert_eq!(matcher.feed(b"cd").collect::<Vec<_>>(), [4]);
+
+        assert_eq!(matcher.feed(b"abcdabcd").next(), Some(8));
+        assert_eq!(matcher.feed(b"abcd").collect::<Vec<_>>(), [16]);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{Finder, Needle};
+
+/// Byte order in which values are encoded in a haystack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// Least significant byte first, as on x86 and most aarch64 systems.
+    Little,
+    /// Most significant byte first, as in network protocols.
+    Big,
+}
+
+impl Endianness {
+    /// Byte order of the target platform.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Self = Self::Little;
+
+    /// Byte order of the target platform.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Self = Self::Big;
+}
+
+/// Primitive value which can be searched for in binary data.
+pub trait Value: Copy {
+    /// Fixed-size array holding the encoded value.
+    type Bytes: Needle;
+
+    /// Encodes the value using the given byte order. Floating-point values
+    /// are encoded as their bit patterns.
+    fn to_bytes(self, endianness: Endianness) -> Self::Bytes;
+}
+
+macro_rules! impl_value {
+    ($($ty:ty),*) => {$(
+        impl Value for $ty {
+            type Bytes = [u8; core::mem::size_of::<$ty>()];
+
+            #[inline]
+            fn to_bytes(self, endianness: Endianness) -> Self::Bytes {
+                match endianness {
+                    Endianness::Little => self.to_le_bytes(),
+                    Endianness::Big => self.to_be_bytes(),
+                }
+            }
+        }
+    )*};
+}
+
+impl_value!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+/// Returns the offset of the first occurrence of `value` encoded with the
+/// given byte order within `haystack`, if any.
+///
+/// The encoded value is searched for as a fixed-size needle, so that the
+/// checks of candidates are specialized for its size.
+///
+/// # Example
+///
+/// ```
+/// use sliceslice::{find_value, Endianness};
+///
+/// let dump = [0x00, 0x00, 0x12, 0x34, 0x56, 0x78, 0x78, 0x56, 0x34, 0x12];
+///
+/// assert_eq!(find_value(&dump, 0x12345678u32, Endianness::Big), Some(2));
+/// assert_eq!(find_value(&dump, 0x12345678u32, Endianness::Little), Some(6));
+/// assert_eq!(find_value(&dump, 0x9abcu16, Endianness::Little), None);
+/// ```
+pub fn find_value<T: Value>(haystack: &[u8], value: T, endianness: Endianness) -> Option<usize> {
+    Finder::new(value.to_bytes(endianness)).find_in(haystack)
+}
+
+/// Same as `find_value` but only reports occurrences at offsets which are
+/// multiples of `stride`, such as the size of `T` for naturally aligned values.
+/// See `Finder::find_aligned_in` for details.
+///
+/// # Panics
+///
+/// Panics if `stride` is zero.
+///
+/// # Example
+///
+/// ```
+/// use sliceslice::{find_aligned_value, Endianness};
+///
+/// let dump = [0x00, 0x00, 0x34, 0x12, 0x00, 0x00, 0x34, 0x12];
+///
+/// assert_eq!(find_aligned_value(&dump, 0x1234u16, Endianness::Little, 1), Some(2));
+/// assert_eq!(find_aligned_value(&dump, 0x1234u16, Endianness::Little, 4), None);
+/// assert_eq!(find_aligned_value(&dump, 0x1234u32, Endianness::Little, 2), Some(2));
+/// ```
+pub fn find_aligned_value<T: Value>(
+    haystack: &[u8],
+    value: T,
+    endianness: Endianness,
+    stride: usize,
+) -> Option<usize> {
+    Finder::new(value.to_bytes(endianness)).find_aligned_in(haystack, stride)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_aligned_value, find_value, Endianness, Value};
+
+    #[test]
+    fn value_to_bytes() {
+        assert_eq!(0x0102u16.to_bytes(Endianness::Little), [0x02, 0x01]);
+        assert_eq!(0x0102u16.to_bytes(Endianness::Big), [0x01, 0x02]);
+        assert_eq!((-2i32).to_bytes(Endianness::Big), [0xff, 0xff, 0xff, 0xfe]);
+        assert_eq!(
+            1.0f64.to_bytes(Endianness::Big),
+            [0x3f, 0xf0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(0x12u8.to_bytes(Endianness::NATIVE), [0x12]);
+        assert_eq!(
+            0x0102_0304u32.to_bytes(Endianness::NATIVE),
+            0x0102_0304u32.to_ne_bytes()
+        );
+    }
+
+    #[test]
+    fn value_search() {
+        let mut dump = vec![0u8; 100];
+        dump[37..45].copy_from_slice(&0xdead_beef_0bad_f00du64.to_le_bytes());
+        dump[64..72].copy_from_slice(&0xdead_beef_0bad_f00du64.to_le_bytes());
+        dump[80..84].copy_from_slice(&(-1.5f32).to_be_bytes());
+
+        let pointer = 0xdead_beef_0bad_f00du64;
+        assert_eq!(find_value(&dump, pointer, Endianness::Little), Some(37));
+        assert_eq!(find_value(&dump, pointer, Endianness::Big), None);
+        assert_eq!(
+            find_aligned_value(&dump, pointer, Endianness::Little, 8),
+            Some(64)
+        );
+        assert_eq!(
+            find_value(&dump, 0xdead_beefu32, Endianness::Little),
+            Some(41)
+        );
+        assert_eq!(find_value(&dump, -1.5f32, Endianness::Big), Some(80));
+        assert_eq!(find_value(&dump, 0i64, Endianness::Big), Some(0));
+        assert_eq!(find_value(&dump, -17i8, Endianness::Big), Some(41));
+        assert_eq!(
+            find_aligned_value(&dump, -17i8, Endianness::Big, 2),
+            Some(68)
+        );
+    }
+}
@@ -1,8 +1,13 @@
 #![allow(clippy::missing_safety_doc)]
 
-use crate::{Needle, NeedleWithSize, Searcher, Vector, VectorHash};
+use crate::{
+    linear_budget, search_in_batch_bits_with, search_in_batch_with, Aligned, Budget,
+    BudgetExceeded, ByteClass, CompiledNeedle, Filter, Needle, NeedleWithSize, Scan, Searcher,
+    Unfiltered, Unlimited, Vector, VectorHash, WordBoundary,
+};
 #[cfg(target_arch = "wasm32")]
-use std::arch::wasm32::*;
+use core::arch::wasm32::*;
+use core::ops::Range;
 
 impl Vector for v128 {
     const LANES: usize = 16;
@@ -17,7 +22,7 @@ impl Vector for v128 {
     #[inline]
     #[target_feature(enable = "simd128")]
     unsafe fn load(a: *const u8) -> Self {
-        std::ptr::read_unaligned(a as *const v128)
+        core::ptr::read_unaligned(a as *const v128)
     }
 
     #[inline]
@@ -57,7 +62,7 @@ impl Vector for v64 {
     #[inline]
     #[target_feature(enable = "simd128")]
     unsafe fn load(a: *const u8) -> Self {
-        Self(u64x2_splat(std::ptr::read_unaligned(a as *const u64)))
+        Self(u64x2_splat(core::ptr::read_unaligned(a as *const u64)))
     }
 
     #[inline]
@@ -103,7 +108,7 @@ impl Vector for v32 {
     #[inline]
     #[target_feature(enable = "simd128")]
     unsafe fn load(a: *const u8) -> Self {
-        Self(u32x4_splat(std::ptr::read_unaligned(a as *const u32)))
+        Self(u32x4_splat(core::ptr::read_unaligned(a as *const u32)))
     }
 
     #[inline]
@@ -149,7 +154,7 @@ impl Vector for v16 {
     #[inline]
     #[target_feature(enable = "simd128")]
     unsafe fn load(a: *const u8) -> Self {
-        Self(u16x8_splat(std::ptr::read_unaligned(a as *const u16)))
+        Self(u16x8_splat(core::ptr::read_unaligned(a as *const u16)))
     }
 
     #[inline]
@@ -238,51 +243,326 @@ impl<N: Needle> Wasm32Searcher<N> {
         }
     }
 
-    /// Inlined version of `search_in` for hot call sites.
+    /// Creates a new searcher from a `CompiledNeedle`, using its needle and
+    /// `position`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the needle is empty or if the associated `SIZE` constant does
+    /// not correspond to the actual size of the needle.
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn from_compiled(compiled: CompiledNeedle<N>) -> Self {
+        let (needle, position) = compiled.into_parts();
+        Self::with_position(needle, position)
+    }
+
     #[inline]
     #[target_feature(enable = "simd128")]
-    pub unsafe fn inlined_search_in(&self, haystack: &[u8]) -> bool {
+    unsafe fn scan<B: Budget, F: Filter>(
+        &self,
+        haystack: &[u8],
+        budget: &mut B,
+        filter: &F,
+    ) -> Scan {
+        self.scan_in(haystack, 0..haystack.len(), budget, filter)
+    }
+
+    /// Same as `scan` but only reports occurrences within `haystack[range]`,
+    /// while still loading chunks from the rest of `haystack`. Offsets are
+    /// relative to the start of `haystack`.
+    #[inline]
+    #[target_feature(enable = "simd128")]
+    unsafe fn scan_in<B: Budget, F: Filter>(
+        &self,
+        haystack: &[u8],
+        range: Range<usize>,
+        budget: &mut B,
+        filter: &F,
+    ) -> Scan {
         if haystack.len() <= self.needle.size() {
-            return haystack == self.needle.as_bytes();
+            return if range == (0..haystack.len()) && haystack == self.needle.as_bytes() {
+                Scan::Found(0)
+            } else {
+                Scan::NotFound
+            };
         }
 
         let end = haystack.len() - self.needle.size() + 1;
+        let positions = match (range.end + 1).checked_sub(self.needle.size()) {
+            Some(limit) if limit > range.start => range.start..limit,
+            _ => return Scan::NotFound,
+        };
 
         if end < v16::LANES {
             unreachable!();
         } else if end < v32::LANES {
             let hash = VectorHash::<v16>::from(&self.v128_hash);
-            self.vector_search_in_simd128_version(haystack, end, &hash)
+            self.vector_search_in_simd128_version(haystack, positions, end, &hash, budget, filter)
         } else if end < v64::LANES {
             let hash = VectorHash::<v32>::from(&self.v128_hash);
-            self.vector_search_in_simd128_version(haystack, end, &hash)
+            self.vector_search_in_simd128_version(haystack, positions, end, &hash, budget, filter)
         } else if end < v128::LANES {
             let hash = VectorHash::<v64>::from(&self.v128_hash);
-            self.vector_search_in_simd128_version(haystack, end, &hash)
+            self.vector_search_in_simd128_version(haystack, positions, end, &hash, budget, filter)
         } else {
-            self.vector_search_in_simd128_version(haystack, end, &self.v128_hash)
+            self.vector_search_in_simd128_version(
+                haystack,
+                positions,
+                end,
+                &self.v128_hash,
+                budget,
+                filter,
+            )
         }
     }
 
+    #[inline]
+    #[target_feature(enable = "simd128")]
+    unsafe fn rscan(&self, haystack: &[u8]) -> Option<usize> {
+        if haystack.len() <= self.needle.size() {
+            return (haystack == self.needle.as_bytes()).then_some(0);
+        }
+
+        let end = haystack.len() - self.needle.size() + 1;
+
+        if end < v16::LANES {
+            unreachable!();
+        } else if end < v32::LANES {
+            let hash = VectorHash::<v16>::from(&self.v128_hash);
+            self.vector_rsearch_in_simd128_version(haystack, end, &hash)
+        } else if end < v64::LANES {
+            let hash = VectorHash::<v32>::from(&self.v128_hash);
+            self.vector_rsearch_in_simd128_version(haystack, end, &hash)
+        } else if end < v128::LANES {
+            let hash = VectorHash::<v64>::from(&self.v128_hash);
+            self.vector_rsearch_in_simd128_version(haystack, end, &hash)
+        } else {
+            self.vector_rsearch_in_simd128_version(haystack, end, &self.v128_hash)
+        }
+    }
+
+    /// Inlined version of `search_in` for hot call sites.
+    #[inline]
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn inlined_search_in(&self, haystack: &[u8]) -> bool {
+        self.scan(haystack, &mut Unlimited, &Unfiltered).is_found()
+    }
+
     /// Performs a substring search for the `needle` within `haystack`.
     #[target_feature(enable = "simd128")]
     pub unsafe fn search_in(&self, haystack: &[u8]) -> bool {
         self.inlined_search_in(haystack)
     }
+
+    /// Performs a substring search for the `needle` within each of `haystacks`,
+    /// writing the results to the corresponding elements of `out`. This avoids
+    /// the overhead of a call per haystack, which dominates for short
+    /// haystacks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `haystacks` and `out` have different lengths.
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn search_in_batch(&self, haystacks: &[&[u8]], out: &mut [bool]) {
+        search_in_batch_with(haystacks, out, |haystack| self.inlined_search_in(haystack))
+    }
+
+    /// Same as `search_in_batch` but writes the results as a bitset, where bit
+    /// `i % 64` of `out[i / 64]` is set if and only if `haystacks[i]` contains
+    /// the `needle`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out.len()` is not `haystacks.len().div_ceil(64)`.
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn search_in_batch_bits(&self, haystacks: &[&[u8]], out: &mut [u64]) {
+        search_in_batch_bits_with(haystacks, out, |haystack| self.inlined_search_in(haystack))
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack`, if any.
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn find_in(&self, haystack: &[u8]) -> Option<usize> {
+        self.scan(haystack, &mut Unlimited, &Unfiltered).into()
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` starting at or after `start`, if any. The offset is relative
+    /// to the start of `haystack`, which allows resuming a search after a
+    /// previous occurrence.
+    ///
+    /// Unlike searching the subslice, the bytes of `haystack` before `start`
+    /// may be read, so that the SIMD scan keeps using full vectors instead of
+    /// falling back to narrower ones near the end of `haystack`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is greater than the length of `haystack`.
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize> {
+        // Only check that `start` is in bounds.
+        let _ = &haystack[start..];
+        self.scan_in(haystack, start..haystack.len(), &mut Unlimited, &Unfiltered)
+            .into()
+    }
+
+    /// Performs a substring search for the `needle` within `haystack[range]`.
+    ///
+    /// Unlike searching the subslice, the bytes of `haystack` after the end of
+    /// `range` may be read, so that the SIMD scan keeps using full vectors
+    /// instead of falling back to narrower ones near the end of the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of `haystack`.
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn search_in_range(&self, haystack: &[u8], range: Range<usize>) -> bool {
+        // Only check that the range is in bounds.
+        let _ = &haystack[range.clone()];
+        self.scan_in(haystack, range, &mut Unlimited, &Unfiltered)
+            .is_found()
+    }
+
+    /// Returns the offset of the last occurrence of the `needle` within
+    /// `haystack`, if any.
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn rfind_in(&self, haystack: &[u8]) -> Option<usize> {
+        self.rscan(haystack)
+    }
+
+    /// Returns the offset of the first position in `haystack` where the
+    /// `needle` could start, judging only from the two bytes used by the SIMD
+    /// hash. The rest of the needle is not verified, but positions where the
+    /// needle would not fit in `haystack` are never returned.
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn find_candidate_in(&self, haystack: &[u8]) -> Option<usize> {
+        if haystack.len() <= self.needle.size() {
+            return self.short_candidate(haystack);
+        }
+
+        self.scan(haystack, &mut 0, &Unfiltered).into_candidate()
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` which is a whole word, i.e. which is neither preceded nor
+    /// followed by a byte of `class`, if any. Occurrences which are not whole
+    /// words are skipped without interrupting the SIMD scan.
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn find_word_in(&self, haystack: &[u8], class: &ByteClass) -> Option<usize> {
+        self.find_word_at(haystack, 0, class)
+    }
+
+    /// Same as `find_word_in` but only reports occurrences starting at or
+    /// after `start`, like `find_at`. The byte before `start` is still checked
+    /// for whole words, so that a search resumed past a previous occurrence
+    /// reports the same occurrences as a search of the whole `haystack`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is greater than the length of `haystack`.
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn find_word_at(
+        &self,
+        haystack: &[u8],
+        start: usize,
+        class: &ByteClass,
+    ) -> Option<usize> {
+        // Only check that `start` is in bounds.
+        let _ = &haystack[start..];
+        let filter = WordBoundary::new(haystack, self.needle.size(), class);
+        self.scan_in(haystack, start..haystack.len(), &mut Unlimited, &filter)
+            .into()
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` at a multiple of `stride`, if any, such as the offsets of
+    /// fields in a table of aligned records. Candidates at other offsets are
+    /// masked out before being verified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn find_aligned_in(&self, haystack: &[u8], stride: usize) -> Option<usize> {
+        self.scan(haystack, &mut Unlimited, &Aligned::new(stride))
+            .into()
+    }
+
+    /// Same as `search_in` but with a worst-case running time linear in the
+    /// lengths of `haystack` and `needle`, falling back to the Two-Way
+    /// algorithm once too many candidate verifications have failed.
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn search_in_linear(&self, haystack: &[u8]) -> bool {
+        let mut budget = linear_budget(haystack.len(), self.needle.size());
+        self.scan(haystack, &mut budget, &Unfiltered)
+            .finish_linear(haystack, self.needle.as_bytes())
+    }
+
+    /// Same as `search_in` but gives up after `max_verifications` candidate
+    /// positions have been compared against the needle.
+    ///
+    /// This bounds the work spent on inputs where the SIMD filter lets through
+    /// many false positives, returning `BudgetExceeded` instead of stalling.
+    #[target_feature(enable = "simd128")]
+    pub unsafe fn search_in_with_budget(
+        &self,
+        haystack: &[u8],
+        max_verifications: usize,
+    ) -> Result<bool, BudgetExceeded> {
+        let mut budget = max_verifications;
+        self.scan(haystack, &mut budget, &Unfiltered).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Wasm32Searcher;
+    use crate::BudgetExceeded;
 
     impl crate::tests::TestSearcher for Wasm32Searcher<&[u8]> {
-        fn with_position(needle: &'static [u8], position: usize) -> Wasm32Searcher<&[u8]> {
+        fn with_position(needle: &'static [u8], position: usize) -> Wasm32Searcher<&'static [u8]> {
             unsafe { Wasm32Searcher::with_position(needle, position) }
         }
 
         fn search_in(&self, haystack: &[u8]) -> bool {
             unsafe { Wasm32Searcher::search_in(self, haystack) }
         }
+
+        fn search_in_linear(&self, haystack: &[u8]) -> bool {
+            unsafe { Wasm32Searcher::search_in_linear(self, haystack) }
+        }
+
+        fn search_in_with_budget(
+            &self,
+            haystack: &[u8],
+            max_verifications: usize,
+        ) -> Result<bool, BudgetExceeded> {
+            unsafe { Wasm32Searcher::search_in_with_budget(self, haystack, max_verifications) }
+        }
+
+        fn find_in(&self, haystack: &[u8]) -> Option<usize> {
+            unsafe { Wasm32Searcher::find_in(self, haystack) }
+        }
+
+        fn rfind_in(&self, haystack: &[u8]) -> Option<usize> {
+            unsafe { Wasm32Searcher::rfind_in(self, haystack) }
+        }
+
+        fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize> {
+            unsafe { Wasm32Searcher::find_at(self, haystack, start) }
+        }
+
+        fn search_in_range(&self, haystack: &[u8], range: core::ops::Range<usize>) -> bool {
+            unsafe { Wasm32Searcher::search_in_range(self, haystack, range) }
+        }
+
+        fn search_in_batch(&self, haystacks: &[&[u8]], out: &mut [bool]) {
+            unsafe { Wasm32Searcher::search_in_batch(self, haystacks, out) }
+        }
+
+        fn search_in_batch_bits(&self, haystacks: &[&[u8]], out: &mut [u64]) {
+            unsafe { Wasm32Searcher::search_in_batch_bits(self, haystacks, out) }
+        }
     }
 
     crate::generate_tests!(wasm32_searcher, Wasm32Searcher);
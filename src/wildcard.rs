@@ -0,0 +1,222 @@
+use crate::{rank::rank, rank::rarest_position, ByteClass, Finder};
+use alloc::{boxed::Box, vec::Vec};
+use memchr::memchr2;
+
+/// Element of a wildcard pattern matching exactly one byte.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Token {
+    /// Matches only the given byte, which is lowercase when matching
+    /// regardless of case.
+    Byte(u8),
+    /// Matches any byte.
+    Any,
+    /// Matches any byte in the set, regardless of the case setting.
+    Class(ByteClass),
+}
+
+impl Token {
+    #[inline]
+    pub(crate) fn matches(&self, byte: u8, case_insensitive: bool) -> bool {
+        match self {
+            Self::Byte(expected) if case_insensitive => *expected == byte.to_ascii_lowercase(),
+            Self::Byte(expected) => *expected == byte,
+            Self::Any => true,
+            Self::Class(set) => set.contains(byte),
+        }
+    }
+}
+
+/// Sequence of tokens between two wildcards matching any sequence of bytes.
+type Segment = Box<[Token]>;
+
+/// Returns `true` if `haystack` starts with a match of `segment`, assuming that
+/// it is at least as long.
+#[inline]
+fn is_match(segment: &[Token], haystack: &[u8], case_insensitive: bool) -> bool {
+    segment
+        .iter()
+        .zip(haystack)
+        .all(|(token, &byte)| token.matches(byte, case_insensitive))
+}
+
+/// Matcher shared by the `LIKE` and glob patterns, which both consist of
+/// segments of single-byte tokens separated by wildcards matching any sequence
+/// of bytes.
+///
+/// The first and last segments are checked at the start and end of the
+/// haystack, while the others are looked for in order in between, using a
+/// `Finder` for their longest run of literal bytes and verifying the whole
+/// segment around each occurrence.
+#[derive(Clone, Debug)]
+pub(crate) struct WildcardMatcher {
+    prefix: Segment,
+    // `None` if the pattern does not contain any wildcard, in which case the
+    // prefix must span the whole haystack.
+    suffix: Option<Segment>,
+    middle: Box<[Infix]>,
+    case_insensitive: bool,
+}
+
+impl WildcardMatcher {
+    /// Creates a matcher for the `segments` of a pattern, in order. Bytes must
+    /// already be lowercase if `case_insensitive` is set.
+    pub(crate) fn new(segments: Vec<Vec<Token>>, case_insensitive: bool) -> Self {
+        let mut segments = segments.into_iter().map(Vec::into_boxed_slice);
+        let prefix = segments.next().unwrap_or_default();
+        let suffix = segments.next_back();
+        let middle = segments
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| Infix::new(segment, case_insensitive))
+            .collect();
+
+        Self {
+            prefix,
+            suffix,
+            middle,
+            case_insensitive,
+        }
+    }
+
+    /// Returns `true` if the whole `haystack` matches the pattern.
+    pub(crate) fn matches(&self, haystack: &[u8]) -> bool {
+        let ci = self.case_insensitive;
+
+        let Some(suffix) = &self.suffix else {
+            return haystack.len() == self.prefix.len() && is_match(&self.prefix, haystack, ci);
+        };
+
+        let Some(end) = haystack.len().checked_sub(suffix.len()) else {
+            return false;
+        };
+        if end < self.prefix.len()
+            || !is_match(&self.prefix, haystack, ci)
+            || !is_match(suffix, &haystack[end..], ci)
+        {
+            return false;
+        }
+
+        // Matching each segment at its leftmost occurrence leaves the most room
+        // for the following ones, so no backtracking is needed.
+        let mut rest = &haystack[self.prefix.len()..end];
+        for infix in self.middle.iter() {
+            match infix.find_in(rest, ci) {
+                Some(start) => rest = &rest[start + infix.segment.len()..],
+                None => return false,
+            }
+        }
+
+        true
+    }
+}
+
+/// Segment surrounded by wildcards, along with the way of finding its
+/// candidate occurrences.
+#[derive(Clone, Debug)]
+struct Infix {
+    segment: Segment,
+    anchor: Anchor,
+    // Offset of the anchor within the segment.
+    offset: usize,
+}
+
+/// Part of a segment searched for to find the candidate occurrences of the
+/// whole segment.
+#[derive(Clone, Debug)]
+enum Anchor {
+    /// Run of bytes which match only themselves.
+    Literal(Finder<Box<[u8]>>),
+    /// Single byte along with its other case.
+    Byte(u8, u8),
+    /// No literal bytes, every position is a candidate.
+    Any,
+}
+
+impl Infix {
+    fn new(segment: Segment, case_insensitive: bool) -> Self {
+        // Letters cannot be part of a run when matching regardless of case.
+        let is_literal = |token: &Token| match token {
+            Token::Byte(byte) => !case_insensitive || !byte.is_ascii_alphabetic(),
+            _ => false,
+        };
+
+        let mut run = 0..0;
+        let mut start = 0;
+        for (i, token) in segment.iter().enumerate() {
+            if !is_literal(token) {
+                start = i + 1;
+            } else if i + 1 - start > run.len() {
+                run = start..i + 1;
+            }
+        }
+
+        if run.len() >= 2 || (run.len() == 1 && !case_insensitive) {
+            let needle = segment[run.clone()]
+                .iter()
+                .filter_map(|token| match token {
+                    Token::Byte(byte) => Some(*byte),
+                    _ => None,
+                })
+                .collect::<Box<[u8]>>();
+            let position = rarest_position(&needle);
+
+            return Self {
+                anchor: Anchor::Literal(Finder::with_position(needle, position)),
+                offset: run.start,
+                segment,
+            };
+        }
+
+        let rarest = segment
+            .iter()
+            .enumerate()
+            .filter_map(|(i, token)| match token {
+                Token::Byte(byte) => Some((i, *byte)),
+                _ => None,
+            })
+            .min_by_key(|&(_, byte)| rank(byte));
+
+        match rarest {
+            Some((offset, byte)) => Self {
+                anchor: Anchor::Byte(byte, byte.to_ascii_uppercase()),
+                offset,
+                segment,
+            },
+            None => Self {
+                anchor: Anchor::Any,
+                offset: 0,
+                segment,
+            },
+        }
+    }
+
+    /// Returns the offset of the first occurrence of the segment in
+    /// `haystack`, if any.
+    fn find_in(&self, haystack: &[u8], case_insensitive: bool) -> Option<usize> {
+        let size = self.segment.len();
+        let last = haystack.len().checked_sub(size)?;
+
+        let mut start = 0;
+        while start <= last {
+            // The anchor must start at most at `last + offset` for the whole
+            // segment to fit in the haystack.
+            let window = &haystack[start + self.offset..];
+            let found = match &self.anchor {
+                Anchor::Literal(finder) => {
+                    let end = last - start + finder.needle().len();
+                    finder.find_in(&window[..end])
+                }
+                Anchor::Byte(lower, upper) => memchr2(*lower, *upper, &window[..=last - start]),
+                Anchor::Any => Some(0),
+            }?;
+
+            let candidate = start + found;
+            if is_match(&self.segment, &haystack[candidate..], case_insensitive) {
+                return Some(candidate);
+            }
+
+            start = candidate + 1;
+        }
+
+        None
+    }
+}
@@ -16,12 +16,18 @@
 
 #![allow(clippy::missing_safety_doc)]
 
-use crate::{MemchrSearcher, Needle, NeedleWithSize, Searcher, Vector, VectorHash};
-use seq_macro::seq;
+use crate::{
+    find_aligned_with, find_word_with, linear_budget, search_in_batch_bits_with,
+    search_in_batch_with, Aligned, Budget, BudgetExceeded, ByteClass, CompiledNeedle, Filter,
+    MemchrSearcher, Needle, NeedleWithSize, Scan, Searcher, Unfiltered, Unlimited, Vector,
+    VectorHash, WordBoundary,
+};
 #[cfg(target_arch = "x86")]
-use std::arch::x86::*;
+use core::arch::x86::*;
 #[cfg(target_arch = "x86_64")]
-use std::arch::x86_64::*;
+use core::arch::x86_64::*;
+use core::ops::Range;
+use seq_macro::seq;
 
 #[derive(Clone, Copy)]
 #[repr(transparent)]
@@ -41,7 +47,7 @@ impl Vector for __m16i {
     #[inline]
     #[target_feature(enable = "avx2")]
     unsafe fn load(a: *const u8) -> Self {
-        __m16i(_mm_set1_epi16(std::ptr::read_unaligned(a as *const i16)))
+        __m16i(_mm_set1_epi16(core::ptr::read_unaligned(a as *const i16)))
     }
 
     #[inline]
@@ -59,7 +65,7 @@ impl Vector for __m16i {
     #[inline]
     #[target_feature(enable = "avx2")]
     unsafe fn to_bitmask(a: Self) -> u32 {
-        std::mem::transmute(_mm_movemask_epi8(a.0) & 0x3)
+        (_mm_movemask_epi8(a.0) & 0x3) as u32
     }
 }
 
@@ -88,7 +94,7 @@ impl Vector for __m32i {
     #[inline]
     #[target_feature(enable = "avx2")]
     unsafe fn load(a: *const u8) -> Self {
-        __m32i(_mm_set1_epi32(std::ptr::read_unaligned(a as *const i32)))
+        __m32i(_mm_set1_epi32(core::ptr::read_unaligned(a as *const i32)))
     }
 
     #[inline]
@@ -106,7 +112,7 @@ impl Vector for __m32i {
     #[inline]
     #[target_feature(enable = "avx2")]
     unsafe fn to_bitmask(a: Self) -> u32 {
-        std::mem::transmute(_mm_movemask_epi8(a.0) & 0xF)
+        (_mm_movemask_epi8(a.0) & 0xF) as u32
     }
 }
 
@@ -135,7 +141,7 @@ impl Vector for __m64i {
     #[inline]
     #[target_feature(enable = "avx2")]
     unsafe fn load(a: *const u8) -> Self {
-        __m64i(_mm_set1_epi64x(std::ptr::read_unaligned(a as *const i64)))
+        __m64i(_mm_set1_epi64x(core::ptr::read_unaligned(a as *const i64)))
     }
 
     #[inline]
@@ -153,7 +159,7 @@ impl Vector for __m64i {
     #[inline]
     #[target_feature(enable = "avx2")]
     unsafe fn to_bitmask(a: Self) -> u32 {
-        std::mem::transmute(_mm_movemask_epi8(a.0) & 0xFF)
+        (_mm_movemask_epi8(a.0) & 0xFF) as u32
     }
 }
 
@@ -195,7 +201,7 @@ impl Vector for __m128i {
     #[inline]
     #[target_feature(enable = "avx2")]
     unsafe fn to_bitmask(a: Self) -> u32 {
-        std::mem::transmute(_mm_movemask_epi8(a))
+        _mm_movemask_epi8(a) as u32
     }
 }
 
@@ -230,7 +236,7 @@ impl Vector for __m256i {
     #[inline]
     #[target_feature(enable = "avx2")]
     unsafe fn to_bitmask(a: Self) -> u32 {
-        std::mem::transmute(_mm256_movemask_epi8(a))
+        _mm256_movemask_epi8(a) as u32
     }
 }
 
@@ -315,45 +321,158 @@ impl<N: Needle> Avx2Searcher<N> {
         }
     }
 
+    /// Creates a new searcher from a `CompiledNeedle`, using its needle and
+    /// `position`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the needle is empty or if the associated `SIZE` constant does
+    /// not correspond to the actual size of the needle.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn from_compiled(compiled: CompiledNeedle<N>) -> Self {
+        let (needle, position) = compiled.into_parts();
+        Self::with_position(needle, position)
+    }
+
     #[inline]
     #[target_feature(enable = "avx2")]
-    unsafe fn sse2_2_search_in(&self, haystack: &[u8], end: usize) -> bool {
+    unsafe fn sse2_2_search_in<B: Budget, F: Filter>(
+        &self,
+        haystack: &[u8],
+        positions: Range<usize>,
+        end: usize,
+        budget: &mut B,
+        filter: &F,
+    ) -> Scan {
         let hash = VectorHash::<__m16i>::from(&self.sse2_hash);
-        self.vector_search_in_avx2_version(haystack, end, &hash)
+        self.vector_search_in_avx2_version(haystack, positions, end, &hash, budget, filter)
     }
 
     #[inline]
     #[target_feature(enable = "avx2")]
-    unsafe fn sse2_4_search_in(&self, haystack: &[u8], end: usize) -> bool {
+    unsafe fn sse2_4_search_in<B: Budget, F: Filter>(
+        &self,
+        haystack: &[u8],
+        positions: Range<usize>,
+        end: usize,
+        budget: &mut B,
+        filter: &F,
+    ) -> Scan {
         let hash = VectorHash::<__m32i>::from(&self.sse2_hash);
-        self.vector_search_in_avx2_version(haystack, end, &hash)
+        self.vector_search_in_avx2_version(haystack, positions, end, &hash, budget, filter)
     }
 
     #[inline]
     #[target_feature(enable = "avx2")]
-    unsafe fn sse2_8_search_in(&self, haystack: &[u8], end: usize) -> bool {
+    unsafe fn sse2_8_search_in<B: Budget, F: Filter>(
+        &self,
+        haystack: &[u8],
+        positions: Range<usize>,
+        end: usize,
+        budget: &mut B,
+        filter: &F,
+    ) -> Scan {
         let hash = VectorHash::<__m64i>::from(&self.sse2_hash);
-        self.vector_search_in_avx2_version(haystack, end, &hash)
+        self.vector_search_in_avx2_version(haystack, positions, end, &hash, budget, filter)
     }
 
     #[inline]
     #[target_feature(enable = "avx2")]
-    unsafe fn sse2_16_search_in(&self, haystack: &[u8], end: usize) -> bool {
-        self.vector_search_in_avx2_version(haystack, end, &self.sse2_hash)
+    unsafe fn sse2_16_search_in<B: Budget, F: Filter>(
+        &self,
+        haystack: &[u8],
+        positions: Range<usize>,
+        end: usize,
+        budget: &mut B,
+        filter: &F,
+    ) -> Scan {
+        self.vector_search_in_avx2_version(
+            haystack,
+            positions,
+            end,
+            &self.sse2_hash,
+            budget,
+            filter,
+        )
     }
 
     #[inline]
     #[target_feature(enable = "avx2")]
-    unsafe fn avx2_search_in(&self, haystack: &[u8], end: usize) -> bool {
-        self.vector_search_in_avx2_version(haystack, end, &self.avx2_hash)
+    unsafe fn avx2_search_in<B: Budget, F: Filter>(
+        &self,
+        haystack: &[u8],
+        positions: Range<usize>,
+        end: usize,
+        budget: &mut B,
+        filter: &F,
+    ) -> Scan {
+        self.vector_search_in_avx2_version(
+            haystack,
+            positions,
+            end,
+            &self.avx2_hash,
+            budget,
+            filter,
+        )
     }
 
-    /// Inlined version of `search_in` for hot call sites.
     #[inline]
     #[target_feature(enable = "avx2")]
-    pub unsafe fn inlined_search_in(&self, haystack: &[u8]) -> bool {
+    unsafe fn scan<B: Budget, F: Filter>(
+        &self,
+        haystack: &[u8],
+        budget: &mut B,
+        filter: &F,
+    ) -> Scan {
+        self.scan_in(haystack, 0..haystack.len(), budget, filter)
+    }
+
+    /// Same as `scan` but only reports occurrences within `haystack[range]`,
+    /// while still loading chunks from the rest of `haystack`. Offsets are
+    /// relative to the start of `haystack`.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn scan_in<B: Budget, F: Filter>(
+        &self,
+        haystack: &[u8],
+        range: Range<usize>,
+        budget: &mut B,
+        filter: &F,
+    ) -> Scan {
+        if haystack.len() <= self.needle.size() {
+            return if range == (0..haystack.len()) && haystack == self.needle.as_bytes() {
+                Scan::Found(0)
+            } else {
+                Scan::NotFound
+            };
+        }
+
+        let end = haystack.len() - self.needle.size() + 1;
+        let positions = match (range.end + 1).checked_sub(self.needle.size()) {
+            Some(limit) if limit > range.start => range.start..limit,
+            _ => return Scan::NotFound,
+        };
+
+        if end < __m16i::LANES {
+            unreachable!();
+        } else if end < __m32i::LANES {
+            self.sse2_2_search_in(haystack, positions, end, budget, filter)
+        } else if end < __m64i::LANES {
+            self.sse2_4_search_in(haystack, positions, end, budget, filter)
+        } else if end < __m128i::LANES {
+            self.sse2_8_search_in(haystack, positions, end, budget, filter)
+        } else if end < __m256i::LANES {
+            self.sse2_16_search_in(haystack, positions, end, budget, filter)
+        } else {
+            self.avx2_search_in(haystack, positions, end, budget, filter)
+        }
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn rscan(&self, haystack: &[u8]) -> Option<usize> {
         if haystack.len() <= self.needle.size() {
-            return haystack == self.needle.as_bytes();
+            return (haystack == self.needle.as_bytes()).then_some(0);
         }
 
         let end = haystack.len() - self.needle.size() + 1;
@@ -361,23 +480,197 @@ impl<N: Needle> Avx2Searcher<N> {
         if end < __m16i::LANES {
             unreachable!();
         } else if end < __m32i::LANES {
-            self.sse2_2_search_in(haystack, end)
+            let hash = VectorHash::<__m16i>::from(&self.sse2_hash);
+            self.vector_rsearch_in_avx2_version(haystack, end, &hash)
         } else if end < __m64i::LANES {
-            self.sse2_4_search_in(haystack, end)
+            let hash = VectorHash::<__m32i>::from(&self.sse2_hash);
+            self.vector_rsearch_in_avx2_version(haystack, end, &hash)
         } else if end < __m128i::LANES {
-            self.sse2_8_search_in(haystack, end)
+            let hash = VectorHash::<__m64i>::from(&self.sse2_hash);
+            self.vector_rsearch_in_avx2_version(haystack, end, &hash)
         } else if end < __m256i::LANES {
-            self.sse2_16_search_in(haystack, end)
+            self.vector_rsearch_in_avx2_version(haystack, end, &self.sse2_hash)
         } else {
-            self.avx2_search_in(haystack, end)
+            self.vector_rsearch_in_avx2_version(haystack, end, &self.avx2_hash)
         }
     }
 
+    /// Inlined version of `search_in` for hot call sites.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn inlined_search_in(&self, haystack: &[u8]) -> bool {
+        self.scan(haystack, &mut Unlimited, &Unfiltered).is_found()
+    }
+
     /// Performs a substring search for the `needle` within `haystack`.
     #[target_feature(enable = "avx2")]
     pub unsafe fn search_in(&self, haystack: &[u8]) -> bool {
         self.inlined_search_in(haystack)
     }
+
+    /// Performs a substring search for the `needle` within each of `haystacks`,
+    /// writing the results to the corresponding elements of `out`. This avoids
+    /// the overhead of a call per haystack, which dominates for short
+    /// haystacks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `haystacks` and `out` have different lengths.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn search_in_batch(&self, haystacks: &[&[u8]], out: &mut [bool]) {
+        search_in_batch_with(haystacks, out, |haystack| self.inlined_search_in(haystack))
+    }
+
+    /// Same as `search_in_batch` but writes the results as a bitset, where bit
+    /// `i % 64` of `out[i / 64]` is set if and only if `haystacks[i]` contains
+    /// the `needle`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out.len()` is not `haystacks.len().div_ceil(64)`.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn search_in_batch_bits(&self, haystacks: &[&[u8]], out: &mut [u64]) {
+        search_in_batch_bits_with(haystacks, out, |haystack| self.inlined_search_in(haystack))
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack`, if any.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_in(&self, haystack: &[u8]) -> Option<usize> {
+        self.scan(haystack, &mut Unlimited, &Unfiltered).into()
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` starting at or after `start`, if any. The offset is relative
+    /// to the start of `haystack`, which allows resuming a search after a
+    /// previous occurrence.
+    ///
+    /// Unlike searching the subslice, the bytes of `haystack` before `start`
+    /// may be read, so that the SIMD scan keeps using full vectors instead of
+    /// falling back to narrower ones near the end of `haystack`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is greater than the length of `haystack`.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize> {
+        // Only check that `start` is in bounds.
+        let _ = &haystack[start..];
+        self.scan_in(haystack, start..haystack.len(), &mut Unlimited, &Unfiltered)
+            .into()
+    }
+
+    /// Performs a substring search for the `needle` within `haystack[range]`.
+    ///
+    /// Unlike searching the subslice, the bytes of `haystack` after the end of
+    /// `range` may be read, so that the SIMD scan keeps using full vectors
+    /// instead of falling back to narrower ones near the end of the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of `haystack`.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn search_in_range(&self, haystack: &[u8], range: Range<usize>) -> bool {
+        // Only check that the range is in bounds.
+        let _ = &haystack[range.clone()];
+        self.scan_in(haystack, range, &mut Unlimited, &Unfiltered)
+            .is_found()
+    }
+
+    /// Returns the offset of the last occurrence of the `needle` within
+    /// `haystack`, if any.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn rfind_in(&self, haystack: &[u8]) -> Option<usize> {
+        self.rscan(haystack)
+    }
+
+    /// Returns the offset of the first position in `haystack` where the
+    /// `needle` could start, judging only from the two bytes used by the SIMD
+    /// hash. The rest of the needle is not verified, but positions where the
+    /// needle would not fit in `haystack` are never returned.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_candidate_in(&self, haystack: &[u8]) -> Option<usize> {
+        if haystack.len() <= self.needle.size() {
+            return self.short_candidate(haystack);
+        }
+
+        self.scan(haystack, &mut 0, &Unfiltered).into_candidate()
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` which is a whole word, i.e. which is neither preceded nor
+    /// followed by a byte of `class`, if any. Occurrences which are not whole
+    /// words are skipped without interrupting the SIMD scan.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_word_in(&self, haystack: &[u8], class: &ByteClass) -> Option<usize> {
+        self.find_word_at(haystack, 0, class)
+    }
+
+    /// Same as `find_word_in` but only reports occurrences starting at or
+    /// after `start`, like `find_at`. The byte before `start` is still checked
+    /// for whole words, so that a search resumed past a previous occurrence
+    /// reports the same occurrences as a search of the whole `haystack`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is greater than the length of `haystack`.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_word_at(
+        &self,
+        haystack: &[u8],
+        start: usize,
+        class: &ByteClass,
+    ) -> Option<usize> {
+        // Only check that `start` is in bounds.
+        let _ = &haystack[start..];
+        let filter = WordBoundary::new(haystack, self.needle.size(), class);
+        self.scan_in(haystack, start..haystack.len(), &mut Unlimited, &filter)
+            .into()
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` at a multiple of `stride`, if any, such as the offsets of
+    /// fields in a table of aligned records. Candidates at other offsets are
+    /// masked out before being verified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_aligned_in(&self, haystack: &[u8], stride: usize) -> Option<usize> {
+        self.scan(haystack, &mut Unlimited, &Aligned::new(stride))
+            .into()
+    }
+
+    /// Same as `search_in` but with a worst-case running time linear in the
+    /// lengths of `haystack` and `needle`.
+    ///
+    /// Failed candidate verifications are counted and, once they exceed the
+    /// number of times the needle fits in the haystack, the remainder of the
+    /// haystack is searched using the Two-Way algorithm instead. This protects
+    /// against inputs crafted so that almost every position is a candidate,
+    /// such as searching for `aaa...ab` in a haystack made only of `a`.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn search_in_linear(&self, haystack: &[u8]) -> bool {
+        let mut budget = linear_budget(haystack.len(), self.needle.size());
+        self.scan(haystack, &mut budget, &Unfiltered)
+            .finish_linear(haystack, self.needle.as_bytes())
+    }
+
+    /// Same as `search_in` but gives up after `max_verifications` candidate
+    /// positions have been compared against the needle.
+    ///
+    /// This bounds the work spent on inputs where the SIMD filter lets through
+    /// many false positives, returning `BudgetExceeded` instead of stalling.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn search_in_with_budget(
+        &self,
+        haystack: &[u8],
+        max_verifications: usize,
+    ) -> Result<bool, BudgetExceeded> {
+        let mut budget = max_verifications;
+        self.scan(haystack, &mut budget, &Unfiltered).into()
+    }
 }
 
 impl<N: Needle> Searcher<N> for Avx2Searcher<N> {
@@ -490,6 +783,14 @@ impl<N: Needle> DynamicAvx2Searcher<N> {
         }
     }
 
+    /// Creates a new searcher from a `CompiledNeedle`, using its needle and
+    /// `position`.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn from_compiled(compiled: CompiledNeedle<N>) -> Self {
+        let (needle, position) = compiled.into_parts();
+        Self::with_position(needle, position)
+    }
+
     /// Inlined version of `search_in` for hot call sites.
     #[inline]
     #[target_feature(enable = "avx2")]
@@ -521,26 +822,366 @@ impl<N: Needle> DynamicAvx2Searcher<N> {
     pub unsafe fn search_in(&self, haystack: &[u8]) -> bool {
         self.inlined_search_in(haystack)
     }
+
+    /// Performs a substring search for the `needle` within each of `haystacks`,
+    /// writing the results to the corresponding elements of `out`. This avoids
+    /// the overhead of a call per haystack, which dominates for short
+    /// haystacks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `haystacks` and `out` have different lengths.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn search_in_batch(&self, haystacks: &[&[u8]], out: &mut [bool]) {
+        match self {
+            Self::N0 => search_in_batch_with(haystacks, out, |_| true),
+            Self::N1(searcher) => search_in_batch_with(haystacks, out, |haystack| {
+                searcher.inlined_search_in(haystack)
+            }),
+            Self::N2(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N3(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N4(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N5(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N6(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N7(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N8(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N9(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N10(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N11(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N12(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N13(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N14(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N15(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N16(searcher) => searcher.search_in_batch(haystacks, out),
+            Self::N(searcher) => searcher.search_in_batch(haystacks, out),
+        }
+    }
+
+    /// Same as `search_in_batch` but writes the results as a bitset, where bit
+    /// `i % 64` of `out[i / 64]` is set if and only if `haystacks[i]` contains
+    /// the `needle`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out.len()` is not `haystacks.len().div_ceil(64)`.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn search_in_batch_bits(&self, haystacks: &[&[u8]], out: &mut [u64]) {
+        match self {
+            Self::N0 => search_in_batch_bits_with(haystacks, out, |_| true),
+            Self::N1(searcher) => search_in_batch_bits_with(haystacks, out, |haystack| {
+                searcher.inlined_search_in(haystack)
+            }),
+            Self::N2(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N3(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N4(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N5(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N6(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N7(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N8(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N9(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N10(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N11(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N12(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N13(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N14(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N15(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N16(searcher) => searcher.search_in_batch_bits(haystacks, out),
+            Self::N(searcher) => searcher.search_in_batch_bits(haystacks, out),
+        }
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack`, if any.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_in(&self, haystack: &[u8]) -> Option<usize> {
+        match self {
+            Self::N0 => Some(0),
+            Self::N1(searcher) => searcher.find_in(haystack),
+            Self::N2(searcher) => searcher.find_in(haystack),
+            Self::N3(searcher) => searcher.find_in(haystack),
+            Self::N4(searcher) => searcher.find_in(haystack),
+            Self::N5(searcher) => searcher.find_in(haystack),
+            Self::N6(searcher) => searcher.find_in(haystack),
+            Self::N7(searcher) => searcher.find_in(haystack),
+            Self::N8(searcher) => searcher.find_in(haystack),
+            Self::N9(searcher) => searcher.find_in(haystack),
+            Self::N10(searcher) => searcher.find_in(haystack),
+            Self::N11(searcher) => searcher.find_in(haystack),
+            Self::N12(searcher) => searcher.find_in(haystack),
+            Self::N13(searcher) => searcher.find_in(haystack),
+            Self::N14(searcher) => searcher.find_in(haystack),
+            Self::N15(searcher) => searcher.find_in(haystack),
+            Self::N16(searcher) => searcher.find_in(haystack),
+            Self::N(searcher) => searcher.find_in(haystack),
+        }
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` starting at or after `start`, if any. See
+    /// `Avx2Searcher::find_at` for details.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize> {
+        match self {
+            Self::N0 => {
+                // Only check that `start` is in bounds.
+                let _ = &haystack[start..];
+                Some(start)
+            }
+            Self::N1(searcher) => Some(start + searcher.find_in(&haystack[start..])?),
+            Self::N2(searcher) => searcher.find_at(haystack, start),
+            Self::N3(searcher) => searcher.find_at(haystack, start),
+            Self::N4(searcher) => searcher.find_at(haystack, start),
+            Self::N5(searcher) => searcher.find_at(haystack, start),
+            Self::N6(searcher) => searcher.find_at(haystack, start),
+            Self::N7(searcher) => searcher.find_at(haystack, start),
+            Self::N8(searcher) => searcher.find_at(haystack, start),
+            Self::N9(searcher) => searcher.find_at(haystack, start),
+            Self::N10(searcher) => searcher.find_at(haystack, start),
+            Self::N11(searcher) => searcher.find_at(haystack, start),
+            Self::N12(searcher) => searcher.find_at(haystack, start),
+            Self::N13(searcher) => searcher.find_at(haystack, start),
+            Self::N14(searcher) => searcher.find_at(haystack, start),
+            Self::N15(searcher) => searcher.find_at(haystack, start),
+            Self::N16(searcher) => searcher.find_at(haystack, start),
+            Self::N(searcher) => searcher.find_at(haystack, start),
+        }
+    }
+
+    /// Performs a substring search for the `needle` within `haystack[range]`.
+    /// See `Avx2Searcher::search_in_range` for details.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn search_in_range(&self, haystack: &[u8], range: Range<usize>) -> bool {
+        match self {
+            Self::N0 => {
+                // Only check that the range is in bounds.
+                let _ = &haystack[range];
+                true
+            }
+            Self::N1(searcher) => searcher.inlined_search_in(&haystack[range]),
+            Self::N2(searcher) => searcher.search_in_range(haystack, range),
+            Self::N3(searcher) => searcher.search_in_range(haystack, range),
+            Self::N4(searcher) => searcher.search_in_range(haystack, range),
+            Self::N5(searcher) => searcher.search_in_range(haystack, range),
+            Self::N6(searcher) => searcher.search_in_range(haystack, range),
+            Self::N7(searcher) => searcher.search_in_range(haystack, range),
+            Self::N8(searcher) => searcher.search_in_range(haystack, range),
+            Self::N9(searcher) => searcher.search_in_range(haystack, range),
+            Self::N10(searcher) => searcher.search_in_range(haystack, range),
+            Self::N11(searcher) => searcher.search_in_range(haystack, range),
+            Self::N12(searcher) => searcher.search_in_range(haystack, range),
+            Self::N13(searcher) => searcher.search_in_range(haystack, range),
+            Self::N14(searcher) => searcher.search_in_range(haystack, range),
+            Self::N15(searcher) => searcher.search_in_range(haystack, range),
+            Self::N16(searcher) => searcher.search_in_range(haystack, range),
+            Self::N(searcher) => searcher.search_in_range(haystack, range),
+        }
+    }
+
+    /// Returns the offset of the last occurrence of the `needle` within
+    /// `haystack`, if any.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn rfind_in(&self, haystack: &[u8]) -> Option<usize> {
+        match self {
+            Self::N0 => Some(haystack.len()),
+            Self::N1(searcher) => searcher.rfind_in(haystack),
+            Self::N2(searcher) => searcher.rfind_in(haystack),
+            Self::N3(searcher) => searcher.rfind_in(haystack),
+            Self::N4(searcher) => searcher.rfind_in(haystack),
+            Self::N5(searcher) => searcher.rfind_in(haystack),
+            Self::N6(searcher) => searcher.rfind_in(haystack),
+            Self::N7(searcher) => searcher.rfind_in(haystack),
+            Self::N8(searcher) => searcher.rfind_in(haystack),
+            Self::N9(searcher) => searcher.rfind_in(haystack),
+            Self::N10(searcher) => searcher.rfind_in(haystack),
+            Self::N11(searcher) => searcher.rfind_in(haystack),
+            Self::N12(searcher) => searcher.rfind_in(haystack),
+            Self::N13(searcher) => searcher.rfind_in(haystack),
+            Self::N14(searcher) => searcher.rfind_in(haystack),
+            Self::N15(searcher) => searcher.rfind_in(haystack),
+            Self::N16(searcher) => searcher.rfind_in(haystack),
+            Self::N(searcher) => searcher.rfind_in(haystack),
+        }
+    }
+
+    /// Returns the offset of the first position in `haystack` where the
+    /// `needle` could start, judging only from the two bytes used by the SIMD
+    /// hash. The rest of the needle is not verified, but positions where the
+    /// needle would not fit in `haystack` are never returned.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_candidate_in(&self, haystack: &[u8]) -> Option<usize> {
+        match self {
+            Self::N0 => Some(0),
+            Self::N1(searcher) => searcher.find_in(haystack),
+            Self::N2(searcher) => searcher.find_candidate_in(haystack),
+            Self::N3(searcher) => searcher.find_candidate_in(haystack),
+            Self::N4(searcher) => searcher.find_candidate_in(haystack),
+            Self::N5(searcher) => searcher.find_candidate_in(haystack),
+            Self::N6(searcher) => searcher.find_candidate_in(haystack),
+            Self::N7(searcher) => searcher.find_candidate_in(haystack),
+            Self::N8(searcher) => searcher.find_candidate_in(haystack),
+            Self::N9(searcher) => searcher.find_candidate_in(haystack),
+            Self::N10(searcher) => searcher.find_candidate_in(haystack),
+            Self::N11(searcher) => searcher.find_candidate_in(haystack),
+            Self::N12(searcher) => searcher.find_candidate_in(haystack),
+            Self::N13(searcher) => searcher.find_candidate_in(haystack),
+            Self::N14(searcher) => searcher.find_candidate_in(haystack),
+            Self::N15(searcher) => searcher.find_candidate_in(haystack),
+            Self::N16(searcher) => searcher.find_candidate_in(haystack),
+            Self::N(searcher) => searcher.find_candidate_in(haystack),
+        }
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` which is a whole word, i.e. which is neither preceded nor
+    /// followed by a byte of `class`, if any. Occurrences which are not whole
+    /// words are skipped without interrupting the SIMD scan.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_word_in(&self, haystack: &[u8], class: &ByteClass) -> Option<usize> {
+        self.find_word_at(haystack, 0, class)
+    }
+
+    /// Same as `find_word_in` but only reports occurrences starting at or
+    /// after `start`. See `Avx2Searcher::find_word_at` for details.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_word_at(
+        &self,
+        haystack: &[u8],
+        start: usize,
+        class: &ByteClass,
+    ) -> Option<usize> {
+        match self {
+            Self::N0 => find_word_with(haystack, start, 0, class, |_| Some(0)),
+            Self::N1(searcher) => {
+                find_word_with(haystack, start, 1, class, |rest| searcher.find_in(rest))
+            }
+            Self::N2(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N3(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N4(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N5(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N6(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N7(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N8(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N9(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N10(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N11(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N12(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N13(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N14(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N15(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N16(searcher) => searcher.find_word_at(haystack, start, class),
+            Self::N(searcher) => searcher.find_word_at(haystack, start, class),
+        }
+    }
+
+    /// Returns the offset of the first occurrence of the `needle` within
+    /// `haystack` at a multiple of `stride`, if any. See
+    /// `Avx2Searcher::find_aligned_in` for details.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_aligned_in(&self, haystack: &[u8], stride: usize) -> Option<usize> {
+        match self {
+            Self::N0 => find_aligned_with(haystack, stride, |_| Some(0)),
+            Self::N1(searcher) => {
+                find_aligned_with(haystack, stride, |rest| searcher.find_in(rest))
+            }
+            Self::N2(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N3(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N4(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N5(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N6(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N7(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N8(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N9(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N10(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N11(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N12(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N13(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N14(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N15(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N16(searcher) => searcher.find_aligned_in(haystack, stride),
+            Self::N(searcher) => searcher.find_aligned_in(haystack, stride),
+        }
+    }
+
+    /// Same as `search_in` but with a worst-case running time linear in the
+    /// lengths of `haystack` and `needle`. See `Avx2Searcher::search_in_linear`
+    /// for details.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn search_in_linear(&self, haystack: &[u8]) -> bool {
+        match self {
+            Self::N0 => true,
+            Self::N1(searcher) => searcher.inlined_search_in(haystack),
+            Self::N2(searcher) => searcher.search_in_linear(haystack),
+            Self::N3(searcher) => searcher.search_in_linear(haystack),
+            Self::N4(searcher) => searcher.search_in_linear(haystack),
+            Self::N5(searcher) => searcher.search_in_linear(haystack),
+            Self::N6(searcher) => searcher.search_in_linear(haystack),
+            Self::N7(searcher) => searcher.search_in_linear(haystack),
+            Self::N8(searcher) => searcher.search_in_linear(haystack),
+            Self::N9(searcher) => searcher.search_in_linear(haystack),
+            Self::N10(searcher) => searcher.search_in_linear(haystack),
+            Self::N11(searcher) => searcher.search_in_linear(haystack),
+            Self::N12(searcher) => searcher.search_in_linear(haystack),
+            Self::N13(searcher) => searcher.search_in_linear(haystack),
+            Self::N14(searcher) => searcher.search_in_linear(haystack),
+            Self::N15(searcher) => searcher.search_in_linear(haystack),
+            Self::N16(searcher) => searcher.search_in_linear(haystack),
+            Self::N(searcher) => searcher.search_in_linear(haystack),
+        }
+    }
+
+    /// Same as `search_in` but gives up after `max_verifications` candidate
+    /// positions have been compared against the needle. See
+    /// `Avx2Searcher::search_in_with_budget` for details.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn search_in_with_budget(
+        &self,
+        haystack: &[u8],
+        max_verifications: usize,
+    ) -> Result<bool, BudgetExceeded> {
+        match self {
+            Self::N0 => Ok(true),
+            Self::N1(searcher) => Ok(searcher.inlined_search_in(haystack)),
+            Self::N2(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N3(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N4(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N5(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N6(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N7(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N8(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N9(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N10(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N11(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N12(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N13(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N14(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N15(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N16(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+            Self::N(searcher) => searcher.search_in_with_budget(haystack, max_verifications),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{Avx2Searcher, DynamicAvx2Searcher};
-    use crate::Needle;
+    use crate::{BudgetExceeded, ByteClass, Needle, Scan, Unfiltered};
 
     #[test]
+    #[cfg(feature = "alloc")]
     #[should_panic]
     fn avx2_invalid_position() {
         unsafe { Avx2Searcher::with_position(b"foo".to_vec().into_boxed_slice(), 3) };
     }
 
     #[test]
+    #[cfg(feature = "alloc")]
     #[should_panic]
     fn dynamic_avx2_invalid_position() {
         unsafe { DynamicAvx2Searcher::with_position(b"foo".to_vec().into_boxed_slice(), 3) };
     }
 
     #[test]
+    #[cfg(feature = "alloc")]
     #[should_panic]
     fn avx2_empty_needle() {
         unsafe { Avx2Searcher::new(Box::new([])) };
@@ -562,6 +1203,122 @@ mod tests {
         unsafe { Avx2Searcher::new(Foo(b"foo")) };
     }
 
+    #[test]
+    fn avx2_linear_fallback() {
+        let needle = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaab";
+        let mut haystack = vec![b'a'; 100];
+        haystack.extend_from_slice(needle);
+
+        let searcher = unsafe { Avx2Searcher::with_position(&needle[..], 0) };
+
+        // Every position is a candidate, so the budget is exhausted by the
+        // first three verifications.
+        let scan = unsafe { searcher.scan(&haystack, &mut 3, &Unfiltered) };
+        assert!(matches!(scan, Scan::Exhausted(3)));
+        assert!(scan.finish_linear(&haystack, needle));
+    }
+
+    #[test]
+    fn avx2_budget_exceeded() {
+        let needle = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaab";
+        let haystack = [b'a'; 1000];
+
+        let searcher = unsafe { Avx2Searcher::with_position(&needle[..], 0) };
+
+        let result = unsafe { searcher.search_in_with_budget(&haystack, 10) };
+        assert_eq!(result.map_err(|error| error.offset()), Err(10));
+
+        let searcher = unsafe { DynamicAvx2Searcher::new(&needle[..]) };
+        let result = unsafe { searcher.search_in_with_budget(&haystack, 0) };
+        assert_eq!(result, Ok(false));
+    }
+
+    #[test]
+    fn avx2_word() {
+        let haystack = b"ab_abc abcabc (abc) abcdefghijklmnopq_ abcdefghijklmnopq abc";
+        let class = ByteClass::word();
+
+        for (needle, expected) in [
+            (&b""[..], Some(14)),
+            (b"a", None),
+            (b"abc", Some(15)),
+            (b"abcabc", Some(7)),
+            (b"abcdefghijklmnopq", Some(39)),
+        ] {
+            let searcher = unsafe { DynamicAvx2Searcher::new(needle) };
+            assert_eq!(unsafe { searcher.find_word_in(haystack, &class) }, expected);
+            assert_eq!(
+                unsafe { searcher.find_word_at(haystack, 0, &class) },
+                expected
+            );
+
+            if needle.len() > 1 {
+                let searcher = unsafe { Avx2Searcher::new(needle) };
+                assert_eq!(unsafe { searcher.find_word_in(haystack, &class) }, expected);
+                assert_eq!(
+                    unsafe { searcher.find_word_at(haystack, 0, &class) },
+                    expected
+                );
+            }
+        }
+
+        // Resuming the search right after a previous occurrence still checks
+        // the byte before it.
+        for (start, expected) in [(1, Some(15)), (8, Some(15)), (16, Some(57)), (58, None)] {
+            let searcher = unsafe { DynamicAvx2Searcher::new(&b"abc"[..]) };
+            assert_eq!(
+                unsafe { searcher.find_word_at(haystack, start, &class) },
+                expected
+            );
+
+            let searcher = unsafe { Avx2Searcher::new(&b"abc"[..]) };
+            assert_eq!(
+                unsafe { searcher.find_word_at(haystack, start, &class) },
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn avx2_aligned() {
+        // Occurrences of `cab` start at offsets 2, 7, 12, 17, ...
+        let haystack = b"abcab".repeat(20);
+
+        for (stride, expected) in [
+            (1, Some(2)),
+            (2, Some(2)),
+            (4, Some(12)),
+            (8, Some(32)),
+            (16, Some(32)),
+            (32, Some(32)),
+            (64, None),
+        ] {
+            let searcher = unsafe { Avx2Searcher::new(b"cab") };
+            assert_eq!(
+                unsafe { searcher.find_aligned_in(&haystack, stride) },
+                expected
+            );
+
+            let searcher = unsafe { DynamicAvx2Searcher::new(b"cab") };
+            assert_eq!(
+                unsafe { searcher.find_aligned_in(&haystack, stride) },
+                expected
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn dynamic_avx2_from_compiled() {
+        use crate::CompiledNeedle;
+
+        let compiled = CompiledNeedle::with_position(b"ipsum".to_vec(), 2);
+        let searcher = unsafe { DynamicAvx2Searcher::from_compiled(compiled) };
+
+        assert!(matches!(searcher, DynamicAvx2Searcher::N5(_)));
+        assert!(unsafe { searcher.search_in(b"Lorem ipsum dolor sit amet") });
+    }
+
     #[test]
     #[cfg(target_pointer_width = "64")]
     fn size_of_avx2_searcher() {
@@ -570,6 +1327,7 @@ mod tests {
         assert_eq!(size_of::<Avx2Searcher::<&[u8]>>(), 128);
         assert_eq!(size_of::<Avx2Searcher::<[u8; 0]>>(), 128);
         assert_eq!(size_of::<Avx2Searcher::<[u8; 16]>>(), 128);
+        #[cfg(feature = "alloc")]
         assert_eq!(size_of::<Avx2Searcher::<Box<[u8]>>>(), 128);
     }
 
@@ -581,29 +1339,105 @@ mod tests {
         assert_eq!(size_of::<DynamicAvx2Searcher::<&[u8]>>(), 160);
         assert_eq!(size_of::<DynamicAvx2Searcher::<[u8; 0]>>(), 160);
         assert_eq!(size_of::<DynamicAvx2Searcher::<[u8; 16]>>(), 160);
+        #[cfg(feature = "alloc")]
         assert_eq!(size_of::<DynamicAvx2Searcher::<Box<[u8]>>>(), 160);
     }
 
     impl crate::tests::TestSearcher for Avx2Searcher<&[u8]> {
-        fn with_position(needle: &'static [u8], position: usize) -> Avx2Searcher<&[u8]> {
+        fn with_position(needle: &'static [u8], position: usize) -> Avx2Searcher<&'static [u8]> {
             unsafe { Avx2Searcher::with_position(needle, position) }
         }
 
         fn search_in(&self, haystack: &[u8]) -> bool {
             unsafe { Avx2Searcher::search_in(self, haystack) }
         }
+
+        fn search_in_linear(&self, haystack: &[u8]) -> bool {
+            unsafe { Avx2Searcher::search_in_linear(self, haystack) }
+        }
+
+        fn search_in_with_budget(
+            &self,
+            haystack: &[u8],
+            max_verifications: usize,
+        ) -> Result<bool, BudgetExceeded> {
+            unsafe { Avx2Searcher::search_in_with_budget(self, haystack, max_verifications) }
+        }
+
+        fn find_in(&self, haystack: &[u8]) -> Option<usize> {
+            unsafe { Avx2Searcher::find_in(self, haystack) }
+        }
+
+        fn rfind_in(&self, haystack: &[u8]) -> Option<usize> {
+            unsafe { Avx2Searcher::rfind_in(self, haystack) }
+        }
+
+        fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize> {
+            unsafe { Avx2Searcher::find_at(self, haystack, start) }
+        }
+
+        fn search_in_range(&self, haystack: &[u8], range: core::ops::Range<usize>) -> bool {
+            unsafe { Avx2Searcher::search_in_range(self, haystack, range) }
+        }
+
+        fn search_in_batch(&self, haystacks: &[&[u8]], out: &mut [bool]) {
+            unsafe { Avx2Searcher::search_in_batch(self, haystacks, out) }
+        }
+
+        fn search_in_batch_bits(&self, haystacks: &[&[u8]], out: &mut [u64]) {
+            unsafe { Avx2Searcher::search_in_batch_bits(self, haystacks, out) }
+        }
     }
 
     crate::generate_tests!(avx2_searcher, Avx2Searcher);
 
     impl crate::tests::TestSearcher for DynamicAvx2Searcher<&[u8]> {
-        fn with_position(needle: &'static [u8], position: usize) -> DynamicAvx2Searcher<&[u8]> {
+        fn with_position(
+            needle: &'static [u8],
+            position: usize,
+        ) -> DynamicAvx2Searcher<&'static [u8]> {
             unsafe { DynamicAvx2Searcher::with_position(needle, position) }
         }
 
         fn search_in(&self, haystack: &[u8]) -> bool {
             unsafe { DynamicAvx2Searcher::search_in(self, haystack) }
         }
+
+        fn search_in_linear(&self, haystack: &[u8]) -> bool {
+            unsafe { DynamicAvx2Searcher::search_in_linear(self, haystack) }
+        }
+
+        fn search_in_with_budget(
+            &self,
+            haystack: &[u8],
+            max_verifications: usize,
+        ) -> Result<bool, BudgetExceeded> {
+            unsafe { DynamicAvx2Searcher::search_in_with_budget(self, haystack, max_verifications) }
+        }
+
+        fn find_in(&self, haystack: &[u8]) -> Option<usize> {
+            unsafe { DynamicAvx2Searcher::find_in(self, haystack) }
+        }
+
+        fn rfind_in(&self, haystack: &[u8]) -> Option<usize> {
+            unsafe { DynamicAvx2Searcher::rfind_in(self, haystack) }
+        }
+
+        fn find_at(&self, haystack: &[u8], start: usize) -> Option<usize> {
+            unsafe { DynamicAvx2Searcher::find_at(self, haystack, start) }
+        }
+
+        fn search_in_range(&self, haystack: &[u8], range: core::ops::Range<usize>) -> bool {
+            unsafe { DynamicAvx2Searcher::search_in_range(self, haystack, range) }
+        }
+
+        fn search_in_batch(&self, haystacks: &[&[u8]], out: &mut [bool]) {
+            unsafe { DynamicAvx2Searcher::search_in_batch(self, haystacks, out) }
+        }
+
+        fn search_in_batch_bits(&self, haystacks: &[&[u8]], out: &mut [u64]) {
+            unsafe { DynamicAvx2Searcher::search_in_batch_bits(self, haystacks, out) }
+        }
     }
 
     crate::generate_tests!(dynamic_avx2_searcher, DynamicAvx2Searcher);
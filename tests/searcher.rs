@@ -0,0 +1,50 @@
+#![cfg(feature = "macros")]
+
+use sliceslice::searcher;
+
+static I386: &[u8] = include_bytes!("../data/i386.txt");
+
+#[test]
+fn searcher_literal() {
+    let finder = searcher!(b"Content-Length:");
+
+    assert!(finder.search_in(b"Host: example.com\r\nContent-Length: 42\r\n"));
+    assert!(!finder.search_in(b"Host: example.com\r\nContent-Type: text/plain\r\n"));
+    assert!(!finder.search_in(b"Content-Length"));
+}
+
+#[test]
+fn searcher_short() {
+    assert!(searcher!(b"").search_in(b"foo"));
+    assert!(searcher!(b"o").search_in(b"foo"));
+    assert!(searcher!(b"oo").search_in(b"foo"));
+    assert!(!searcher!(b"of").search_in(b"foo"));
+}
+
+#[test]
+fn searcher_long_haystack() {
+    let haystack = String::from_utf8_lossy(I386);
+
+    macro_rules! check {
+        ($($needle:literal),*) => {$(
+            assert_eq!(
+                searcher!($needle).search_in(haystack.as_bytes()),
+                haystack.contains(std::str::from_utf8($needle).unwrap()),
+            );
+        )*};
+    }
+
+    check!(
+        b"ADD",
+        b"ADDPS",
+        b"Exception",
+        b"instruction",
+        b"Protected Mode",
+        b"segment selector",
+        b"Interrupt descriptor table",
+        b"the processor generates a general-protection exception",
+        b"not present in this document at all",
+        b"abcdefghijklmnopqrstuvwxyz0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        b"abcdefghijklmnopqrstuvwxyz0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"
+    );
+}